@@ -0,0 +1,37 @@
+// Native helper for generating Merkle proofs off-chain.
+// Read leaves from stdin (one per line) and print
+// root and proofs as JSON.
+//
+// Run: `cargo run --example merkle_proofs -- keccak < leaves.txt`
+use std::io::{self, BufRead};
+
+use serde::Serialize;
+use xpx_supercontracts_sdk::merkle::{HashAlgorithm, MerkleProof, MerkleTree, PairOrdering};
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Output {
+    root: String,
+    leaves: Vec<String>,
+    proofs: Vec<MerkleProof>,
+}
+
+fn main() {
+    let algorithm = match std::env::args().nth(1).as_deref() {
+        Some("keccak") => HashAlgorithm::Keccak256,
+        _ => HashAlgorithm::Sha3_256,
+    };
+    let leaves: Vec<String> = io::stdin()
+        .lock()
+        .lines()
+        .map(|line| line.unwrap())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let tree = MerkleTree::new(&leaves, algorithm, PairOrdering::Sorted);
+    let output = Output {
+        root: tree.root_hex(),
+        proofs: tree.proofs(),
+        leaves,
+    };
+    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+}
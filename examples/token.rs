@@ -0,0 +1,39 @@
+use xpx_supercontracts_sdk::token::{Fungible, FungibleDefinition};
+use xpx_supercontracts_sdk::transactions::get_supercontract;
+use xpx_supercontracts_sdk::transactions_type::{
+    FUNCTION_CONSTRUCTOR_FAIL_TO_SAVE, FUNCTION_ERROR, FUNCTION_RETURN_SUCCESS,
+};
+use xpx_supercontracts_sdk::utils::{constructor, debug_message};
+
+fn create_token() -> i64 {
+    let sc = match get_supercontract() {
+        Ok(sc) => sc,
+        Err(_) => return FUNCTION_ERROR,
+    };
+
+    let token = Fungible::define(&FungibleDefinition {
+        owner_public_key: sc.id,
+        nonce: 0,
+        divisibility: 6,
+        initial_supply: 1_000_000_000_000,
+        supply_mutable: true,
+        transferable: true,
+    });
+    let token = match token {
+        Ok(token) => token,
+        Err(_) => return FUNCTION_ERROR,
+    };
+    if token.register_alias("my_token", 1000).is_err() {
+        debug_message("failed register token alias");
+    }
+    FUNCTION_RETURN_SUCCESS
+}
+
+#[no_mangle]
+pub extern "C" fn app_main() -> i64 {
+    let res = constructor(create_token);
+    if res == FUNCTION_CONSTRUCTOR_FAIL_TO_SAVE {
+        return res;
+    }
+    FUNCTION_RETURN_SUCCESS
+}
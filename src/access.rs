@@ -0,0 +1,295 @@
+//! # Access control
+//!
+//! Owner and named roles of SuperContract. Owner set once
+//! at construction time with `init_owner`, and can grant or revoke
+//! roles. Guards `require_owner` and `require_role` compare
+//! initiator of Execute transaction with stored state.
+//!
+//! Ownership transferred in two steps: current owner
+//! nominate new owner with `transfer_ownership`, and new
+//! owner confirm it with `accept_ownership`. So ownership
+//! can't be lost because of mistyped public key.
+//!
+//! State persisted in Storage file `access/acl.json`.
+//! Public keys compared case-insensitively.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::statuses::{check_status, Error, Result};
+use crate::storage::{save_result, storage_get};
+use crate::transactions::get_initiator_pubkey;
+use crate::transactions_type::PubKey;
+use crate::utils::{try_once, OnceScope};
+
+const ACL_FILE: &str = "access/acl.json";
+const INIT_OWNER_GUARD: &str = "access/init_owner";
+
+/// Access control state saved to Storage
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AccessControl {
+    pub owner: Option<PubKey>,
+    pub pending_owner: Option<PubKey>,
+    /// Members of every role
+    pub roles: BTreeMap<String, BTreeSet<PubKey>>,
+}
+
+impl AccessControl {
+    /// Load state from Storage
+    pub fn load() -> Result<Self> {
+        let data = storage_get(&ACL_FILE.to_string())?;
+        if data.is_empty() {
+            return Ok(AccessControl::default());
+        }
+        serde_json::from_slice(&data).map_err(|_| Error::DeserializeJson)
+    }
+
+    /// Save state to Storage
+    pub fn save(&self) -> Result<()> {
+        let data = serde_json::to_vec(self).map_err(|_| Error::SerializeJson)?;
+        check_status(save_result(&ACL_FILE.to_string(), &data)?)?;
+        Ok(())
+    }
+
+    /// Is public key owner of SuperContract
+    pub fn is_owner(&self, pub_key: &str) -> bool {
+        self.owner.as_ref().is_some_and(|owner| same_key(owner, pub_key))
+    }
+
+    /// Is public key member of role
+    pub fn has_role(&self, role: &str, pub_key: &str) -> bool {
+        self.roles
+            .get(role)
+            .is_some_and(|members| members.contains(&normalize(pub_key)))
+    }
+
+    fn grant(&mut self, role: &str, pub_key: &str) {
+        self.roles
+            .entry(role.to_string())
+            .or_default()
+            .insert(normalize(pub_key));
+    }
+
+    /// Empty role removed, so it isn't kept in Storage
+    fn revoke(&mut self, role: &str, pub_key: &str) {
+        if let Some(members) = self.roles.get_mut(role) {
+            members.remove(&normalize(pub_key));
+            if members.is_empty() {
+                self.roles.remove(role);
+            }
+        }
+    }
+}
+
+fn normalize(pub_key: &str) -> PubKey {
+    pub_key.to_uppercase()
+}
+
+fn same_key(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// Current owner of SuperContract
+pub fn owner() -> Result<Option<PubKey>> {
+    Ok(AccessControl::load()?.owner)
+}
+
+/// Set initiator as owner of SuperContract at construction time.
+/// Function use own named guard of `utils::try_once`, so owner
+/// can be set only once, and `utils::constructor` is still
+/// available for other construction steps. Guard marked as done
+/// only after owner saved, so failed `init_owner` can be retried.
+/// Return `Error::OwnerAlreadySet` if owner already set.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::access::init_owner;
+///
+/// #[no_mangle]
+/// pub extern "C" fn app_main() -> i64 {
+///     match init_owner() {
+///         Ok(owner) => {
+///             // Other construction steps
+///         }
+///         Err(err) => {
+///             // Owner already set
+///         }
+///     }
+///     0
+/// }
+/// ```
+pub fn init_owner() -> Result<PubKey> {
+    let owner = try_once(OnceScope::Contract, INIT_OWNER_GUARD, || {
+        let initiator = get_initiator_pubkey()?;
+        let mut acl = AccessControl::load()?;
+        if acl.owner.is_some() {
+            return Err(Error::OwnerAlreadySet);
+        }
+        let owner = normalize(&initiator);
+        acl.owner = Some(owner.clone());
+        acl.save()?;
+        Ok(owner)
+    })?;
+    owner.ok_or(Error::OwnerAlreadySet)
+}
+
+/// Check is initiator owner of SuperContract.
+/// Return initiator public key or `Error::NotOwner`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::access::require_owner;
+///
+/// #[no_mangle]
+/// pub extern "C" fn withdraw() -> i64 {
+///     if let Err(err) = require_owner() {
+///         return err as i64;
+///     }
+///     // Owner-only logic
+///     0
+/// }
+/// ```
+pub fn require_owner() -> Result<PubKey> {
+    let initiator = get_initiator_pubkey()?;
+    if !AccessControl::load()?.is_owner(&initiator) {
+        return Err(Error::NotOwner);
+    }
+    Ok(initiator)
+}
+
+/// Check is initiator has role. Owner doesn't have
+/// roles implicitly, it should grant role to itself.
+/// Return initiator public key or `Error::MissingRole`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::access::require_role;
+///
+/// #[no_mangle]
+/// pub extern "C" fn mint() -> i64 {
+///     if let Err(err) = require_role("minter") {
+///         return err as i64;
+///     }
+///     // Minter-only logic
+///     0
+/// }
+/// ```
+pub fn require_role(role: &str) -> Result<PubKey> {
+    let initiator = get_initiator_pubkey()?;
+    if !AccessControl::load()?.has_role(role, &initiator) {
+        return Err(Error::MissingRole);
+    }
+    Ok(initiator)
+}
+
+/// Is public key member of role
+pub fn has_role(role: &str, pub_key: &str) -> Result<bool> {
+    Ok(AccessControl::load()?.has_role(role, pub_key))
+}
+
+/// Grant role to public key. Only owner can grant roles.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::access::grant_role;
+/// let minter = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C";
+/// let result = grant_role("minter", minter);
+/// ```
+pub fn grant_role(role: &str, pub_key: &str) -> Result<()> {
+    require_owner()?;
+    let mut acl = AccessControl::load()?;
+    acl.grant(role, pub_key);
+    acl.save()
+}
+
+/// Revoke role from public key. Only owner can revoke roles.
+pub fn revoke_role(role: &str, pub_key: &str) -> Result<()> {
+    require_owner()?;
+    let mut acl = AccessControl::load()?;
+    acl.revoke(role, pub_key);
+    acl.save()
+}
+
+/// Nominate new owner. Ownership changed only after
+/// new owner invoke `accept_ownership`. Only owner
+/// can nominate new owner.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::access::transfer_ownership;
+/// let new_owner = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C";
+/// let result = transfer_ownership(new_owner);
+/// ```
+pub fn transfer_ownership(new_owner: &str) -> Result<()> {
+    require_owner()?;
+    let mut acl = AccessControl::load()?;
+    acl.pending_owner = Some(normalize(new_owner));
+    acl.save()
+}
+
+/// Accept ownership nominated by `transfer_ownership`.
+/// Return new owner or `Error::NotPendingOwner` if
+/// initiator wasn't nominated.
+pub fn accept_ownership() -> Result<PubKey> {
+    let initiator = get_initiator_pubkey()?;
+    let mut acl = AccessControl::load()?;
+    match acl.pending_owner.take() {
+        Some(pending) if same_key(&pending, &initiator) => {
+            acl.owner = Some(pending.clone());
+            acl.save()?;
+            Ok(pending)
+        }
+        _ => Err(Error::NotPendingOwner),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: &str = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C";
+    const MINTER: &str = "A1B2C3D4E5F60718293A4B5C6D7E8F90A1B2C3D4E5F60718293A4B5C6D7E8F90";
+
+    #[test]
+    fn owner_compared_case_insensitively() {
+        let acl = AccessControl {
+            owner: Some(OWNER.to_string()),
+            ..AccessControl::default()
+        };
+        assert!(acl.is_owner(OWNER));
+        assert!(acl.is_owner(&OWNER.to_lowercase()));
+        assert!(!acl.is_owner(MINTER));
+        assert!(!AccessControl::default().is_owner(OWNER));
+    }
+
+    #[test]
+    fn role_compared_case_insensitively() {
+        let mut acl = AccessControl::default();
+        acl.grant("minter", &MINTER.to_lowercase());
+        assert!(acl.has_role("minter", MINTER));
+        assert!(acl.has_role("minter", &MINTER.to_lowercase()));
+        assert!(!acl.has_role("minter", OWNER));
+        assert!(!acl.has_role("burner", MINTER));
+    }
+
+    #[test]
+    fn revoked_member_has_no_role() {
+        let mut acl = AccessControl::default();
+        acl.grant("minter", MINTER);
+        acl.grant("minter", OWNER);
+
+        acl.revoke("minter", &MINTER.to_lowercase());
+        assert!(!acl.has_role("minter", MINTER));
+        assert!(acl.has_role("minter", OWNER));
+
+        acl.revoke("minter", OWNER);
+        assert!(!acl.has_role("minter", OWNER));
+        assert!(acl.roles.is_empty());
+
+        // Revoking unknown member or role is no-op
+        acl.revoke("burner", OWNER);
+        assert_eq!(acl, AccessControl::default());
+    }
+}
@@ -0,0 +1,381 @@
+//! # Resumable batch processing
+//!
+//! Long jobs, like thousands of `Transfer` transactions from
+//! CSV file, can't be completed in one SuperContract execution.
+//! `Batch` process work list step by step, periodically `flush`
+//! Transactions Pool and save checkpoint to Storage. Next
+//! execution continue from saved cursor.
+//!
+//! Every item has idempotency key, unique for work list row.
+//! Keys of recently processed items saved in checkpoint together
+//! with cursor, so item is not processed twice if work list was
+//! slightly changed between executions. Number of saved keys
+//! limited by `key_window`, so checkpoint size doesn't grow
+//! with work list.
+//!
+//! Transactions Pool can't be cleared, so item processing should
+//! be atomic: `process` should validate item first and enqueue
+//! either all transactions of item or none. Transactions of
+//! failed item enqueued before failure are sent with the next
+//! `flush`, and item is processed again on the next run.
+//!
+//! Checkpoint located in `batch/<name>.json` file.
+
+use std::collections::{HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::gas::Budget;
+use crate::statuses::{check_status, Error, Result};
+use crate::storage::{save_result, storage_get};
+use crate::transactions::flush;
+
+/// Default number of items between `flush` invocations
+pub const DEFAULT_FLUSH_EVERY: usize = 100;
+
+/// Default number of idempotency keys kept in checkpoint
+pub const DEFAULT_KEY_WINDOW: usize = 1000;
+
+/// Batch progress saved to Storage
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Checkpoint {
+    /// Index of next item in work list
+    pub cursor: u64,
+    /// Number of processed items
+    pub processed: u64,
+    /// Idempotency keys of recently processed items, oldest first
+    pub recent_keys: VecDeque<String>,
+    /// Is all work list processed
+    pub completed: bool,
+}
+
+/// Result of batch execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchStatus {
+    /// All items processed
+    Completed { processed: u64 },
+    /// Execution stopped because of `Gas`, should be
+    /// continued in the next execution from `cursor`
+    Paused { cursor: u64, processed: u64 },
+}
+
+/// Resumable batch of work items.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::batch::{Batch, BatchStatus};
+/// use xpx_supercontracts_sdk::transactions::transfer;
+/// use xpx_supercontracts_sdk::transactions_type::{Mosaic, Transfer};
+///
+/// // Row id, recipient and amount. Recipient can be paid several times,
+/// // so row id used as idempotency key.
+/// let payouts = vec![
+///     (1, "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string(), 100),
+///     (2, "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string(), 50),
+/// ];
+/// let status = Batch::new("payouts-2020-01")
+///     .flush_every(50)
+///     .gas_reserve(20_000)
+///     .run(
+///         &payouts,
+///         |(row_id, _, _)| row_id.to_string(),
+///         |(_, pub_key, amount)| {
+///             transfer(&Transfer {
+///                 pub_key: pub_key.clone(),
+///                 asset_id: 992621222383397347,
+///                 amount: *amount,
+///             })
+///         },
+///     );
+/// match status {
+///     Ok(BatchStatus::Completed { processed }) => {}
+///     Ok(BatchStatus::Paused { cursor, .. }) => {
+///         // Continue in the next execution
+///     }
+///     Err(err) => {}
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Batch {
+    name: String,
+    flush_every: usize,
+    gas_reserve: i64,
+    key_window: usize,
+}
+
+impl Batch {
+    /// Create batch with unique name
+    pub fn new(name: &str) -> Self {
+        Batch {
+            name: name.to_string(),
+            flush_every: DEFAULT_FLUSH_EVERY,
+            gas_reserve: 0,
+            key_window: DEFAULT_KEY_WINDOW,
+        }
+    }
+
+    /// Number of items between `flush` and checkpoint.
+    /// `0` means flush only at the end of execution.
+    pub fn flush_every(mut self, items: usize) -> Self {
+        self.flush_every = items;
+        self
+    }
+
+    /// `Gas` kept for final `flush` and checkpoint saving
+    pub fn gas_reserve(mut self, gas: i64) -> Self {
+        self.gas_reserve = gas;
+        self
+    }
+
+    /// Number of recent idempotency keys kept in checkpoint.
+    /// Item moved in work list further than window
+    /// can be processed again.
+    pub fn key_window(mut self, keys: usize) -> Self {
+        self.key_window = keys;
+        self
+    }
+
+    /// Checkpoint file name in Storage
+    pub fn file_name(&self) -> String {
+        format!("batch/{}.json", self.name)
+    }
+
+    /// Load saved checkpoint. Return empty checkpoint
+    /// if batch not started yet.
+    pub fn checkpoint(&self) -> Result<Checkpoint> {
+        let data = storage_get(&self.file_name())?;
+        if data.is_empty() {
+            return Ok(Checkpoint::default());
+        }
+        serde_json::from_slice(&data).map_err(|_| Error::DeserializeJson)
+    }
+
+    /// Start batch from the beginning on the next run
+    pub fn reset(&self) -> Result<()> {
+        self.save(&Checkpoint::default())
+    }
+
+    fn save(&self, checkpoint: &Checkpoint) -> Result<()> {
+        let data = serde_json::to_vec(checkpoint).map_err(|_| Error::SerializeJson)?;
+        check_status(save_result(&self.file_name(), &data)?)?;
+        Ok(())
+    }
+
+    fn commit(&self, checkpoint: &Checkpoint) -> Result<()> {
+        check_status(flush()?)?;
+        self.save(checkpoint)
+    }
+
+    /// Process work list from saved cursor. `key` return idempotency
+    /// key of item, `process` atomically enqueue transactions for item.
+    /// If `process` fail or return negative status, Transactions Pool
+    /// flushed, progress before failed item saved and error returned.
+    pub fn run<T, K, F>(&self, items: &[T], key: K, process: F) -> Result<BatchStatus>
+    where
+        K: Fn(&T) -> String,
+        F: FnMut(&T) -> Result<i64>,
+    {
+        let checkpoint = self.checkpoint()?;
+        let mut budget = Budget::new(self.gas_reserve);
+        self.run_from(
+            checkpoint,
+            items,
+            key,
+            process,
+            || budget.check().is_ok(),
+            |checkpoint| self.commit(checkpoint),
+        )
+    }
+
+    /// Batch loop independent of `WasmVM`: `has_gas` check is
+    /// enough `Gas` for the next item, `commit` flush Transactions
+    /// Pool and save checkpoint.
+    fn run_from<T, K, F, G, C>(
+        &self,
+        mut checkpoint: Checkpoint,
+        items: &[T],
+        key: K,
+        mut process: F,
+        mut has_gas: G,
+        mut commit: C,
+    ) -> Result<BatchStatus>
+    where
+        K: Fn(&T) -> String,
+        F: FnMut(&T) -> Result<i64>,
+        G: FnMut() -> bool,
+        C: FnMut(&Checkpoint) -> Result<()>,
+    {
+        if checkpoint.completed {
+            return Ok(BatchStatus::Completed {
+                processed: checkpoint.processed,
+            });
+        }
+        let mut recent: HashSet<String> = checkpoint.recent_keys.iter().cloned().collect();
+
+        let mut pending = 0;
+        let start = (checkpoint.cursor as usize).min(items.len());
+        for (index, item) in items.iter().enumerate().skip(start) {
+            let item_key = key(item);
+            if !recent.contains(&item_key) {
+                if !has_gas() {
+                    commit(&checkpoint)?;
+                    return Ok(BatchStatus::Paused {
+                        cursor: checkpoint.cursor,
+                        processed: checkpoint.processed,
+                    });
+                }
+                if let Err(err) = process(item).and_then(check_status) {
+                    commit(&checkpoint)?;
+                    return Err(err);
+                }
+                checkpoint.processed += 1;
+                recent.insert(item_key.clone());
+                checkpoint.recent_keys.push_back(item_key);
+                while checkpoint.recent_keys.len() > self.key_window {
+                    if let Some(key) = checkpoint.recent_keys.pop_front() {
+                        recent.remove(&key);
+                    }
+                }
+                pending += 1;
+            }
+            checkpoint.cursor = index as u64 + 1;
+
+            if self.flush_every > 0 && pending >= self.flush_every {
+                commit(&checkpoint)?;
+                pending = 0;
+            }
+        }
+
+        checkpoint.cursor = items.len() as u64;
+        checkpoint.completed = true;
+        commit(&checkpoint)?;
+        Ok(BatchStatus::Completed {
+            processed: checkpoint.processed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    /// Run batch with `gas` items allowed, return status,
+    /// processed items and last committed checkpoint
+    fn run(
+        batch: &Batch,
+        checkpoint: Checkpoint,
+        items: &[String],
+        gas: usize,
+        fail_on: Option<&str>,
+    ) -> (Result<BatchStatus>, Vec<String>, Checkpoint) {
+        let mut processed = vec![];
+        let mut committed = checkpoint.clone();
+        let mut gas = gas;
+        let status = batch.run_from(
+            checkpoint,
+            items,
+            |item| item.clone(),
+            |item| {
+                if fail_on == Some(item.as_str()) {
+                    return Ok(-1);
+                }
+                processed.push(item.clone());
+                Ok(0)
+            },
+            || {
+                gas = gas.saturating_sub(1);
+                gas > 0
+            },
+            |checkpoint| {
+                committed = checkpoint.clone();
+                Ok(())
+            },
+        );
+        (status, processed, committed)
+    }
+
+    #[test]
+    fn paused_batch_resumed_from_cursor() {
+        let batch = Batch::new("test").flush_every(2);
+        let list = items(&["a", "b", "c", "d", "e"]);
+
+        let (status, processed, checkpoint) = run(&batch, Checkpoint::default(), &list, 4, None);
+        assert_eq!(
+            status.unwrap(),
+            BatchStatus::Paused {
+                cursor: 3,
+                processed: 3
+            }
+        );
+        assert_eq!(processed, items(&["a", "b", "c"]));
+
+        let (status, processed, checkpoint) = run(&batch, checkpoint, &list, 100, None);
+        assert_eq!(status.unwrap(), BatchStatus::Completed { processed: 5 });
+        assert_eq!(processed, items(&["d", "e"]));
+        assert!(checkpoint.completed);
+
+        let (status, processed, _) = run(&batch, checkpoint, &list, 100, None);
+        assert_eq!(status.unwrap(), BatchStatus::Completed { processed: 5 });
+        assert!(processed.is_empty());
+    }
+
+    #[test]
+    fn shifted_items_not_processed_twice() {
+        let batch = Batch::new("test");
+        let (_, _, checkpoint) = run(
+            &batch,
+            Checkpoint::default(),
+            &items(&["a", "b", "c"]),
+            3,
+            None,
+        );
+        assert_eq!(checkpoint.cursor, 2);
+
+        // Item inserted before cursor, so "b" moved to cursor position
+        let list = items(&["new", "a", "b", "c"]);
+        let (status, processed, _) = run(&batch, checkpoint, &list, 100, None);
+        assert_eq!(status.unwrap(), BatchStatus::Completed { processed: 3 });
+        assert_eq!(processed, items(&["c"]));
+    }
+
+    #[test]
+    fn keys_limited_by_window() {
+        let batch = Batch::new("test").key_window(2);
+        let list = items(&["a", "b", "c", "d"]);
+        let (_, _, checkpoint) = run(&batch, Checkpoint::default(), &list, 100, None);
+        assert_eq!(
+            checkpoint.recent_keys,
+            vec!["c".to_string(), "d".to_string()]
+        );
+
+        // Keys in window skipped, key out of window processed again
+        let checkpoint = Checkpoint {
+            completed: false,
+            cursor: 0,
+            ..checkpoint
+        };
+        let (_, processed, _) = run(&batch, checkpoint, &items(&["c", "d", "a"]), 100, None);
+        assert_eq!(processed, items(&["a"]));
+    }
+
+    #[test]
+    fn failed_item_retried_on_next_run() {
+        let batch = Batch::new("test").flush_every(10);
+        let list = items(&["a", "b", "c"]);
+        let (status, processed, checkpoint) =
+            run(&batch, Checkpoint::default(), &list, 100, Some("b"));
+        assert!(status.is_err());
+        assert_eq!(processed, items(&["a"]));
+        assert_eq!(checkpoint.cursor, 1);
+        assert_eq!(checkpoint.processed, 1);
+
+        let (status, processed, _) = run(&batch, checkpoint, &list, 100, None);
+        assert_eq!(status.unwrap(), BatchStatus::Completed { processed: 3 });
+        assert_eq!(processed, items(&["b", "c"]));
+    }
+}
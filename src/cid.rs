@@ -0,0 +1,464 @@
+//! # Content addressing
+//!
+//! Parse, compute and verify content IDs (CID) of Drive files.
+//! `transactions_type::Cid` is string representation of CID,
+//! and `ContentId` is parsed form of it.
+//!
+//! Supported:
+//! * CIDv0 - base58btc encoded `sha2-256` multihash of `dag-pb` node
+//! * CIDv1 - multibase `base58btc` (`z`), `base32` (`b`, `B`) and
+//!   `base16` (`f`, `F`), with `dag-pb` and `raw` codecs
+//!
+//! Files on Drive stored as `dag-pb` UnixFS balanced DAG
+//! with 256 KiB chunks, the same as `ipfs add` default.
+
+use sha2::{Digest, Sha256};
+
+use crate::statuses::{Error, Result};
+use crate::storage::storage_get;
+
+/// Multicodec code of `dag-pb`
+pub const CODEC_DAG_PB: u64 = 0x70;
+/// Multicodec code of `raw`
+pub const CODEC_RAW: u64 = 0x55;
+/// Multihash code of `sha2-256`
+pub const HASH_SHA2_256: u64 = 0x12;
+
+/// Chunk size of Drive files
+pub const CHUNK_SIZE: usize = 256 * 1024;
+/// Max number of links in one DAG node
+pub const MAX_LINKS: usize = 174;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Self-describing hash digest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multihash {
+    pub code: u64,
+    pub digest: Vec<u8>,
+}
+
+impl Multihash {
+    /// Calculate `sha2-256` multihash of data
+    pub fn sha2_256(data: &[u8]) -> Self {
+        Multihash {
+            code: HASH_SHA2_256,
+            digest: Sha256::digest(data).to_vec(),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = vec![];
+        write_varint(&mut result, self.code);
+        write_varint(&mut result, self.digest.len() as u64);
+        result.extend_from_slice(&self.digest);
+        result
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self> {
+        let (code, data) = read_varint(data)?;
+        let (len, digest) = read_varint(data)?;
+        if digest.len() as u64 != len {
+            return Err(Error::InvalidCid);
+        }
+        Ok(Multihash {
+            code,
+            digest: digest.to_vec(),
+        })
+    }
+}
+
+/// Parsed content ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentId {
+    pub version: u8,
+    pub codec: u64,
+    pub hash: Multihash,
+}
+
+impl ContentId {
+    /// Parse CID string.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use xpx_supercontracts_sdk::cid::{ContentId, CODEC_DAG_PB};
+    /// let cid = ContentId::parse("QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH").unwrap();
+    /// assert_eq!(cid.version, 0);
+    /// assert_eq!(cid.codec, CODEC_DAG_PB);
+    /// ```
+    pub fn parse(cid: &str) -> Result<Self> {
+        if cid.len() == 46 && cid.starts_with("Qm") {
+            let hash = Multihash::from_bytes(&base58_decode(cid)?)?;
+            return Ok(ContentId {
+                version: 0,
+                codec: CODEC_DAG_PB,
+                hash,
+            });
+        }
+        let mut chars = cid.chars();
+        let bytes = match chars.next() {
+            Some('z') => base58_decode(chars.as_str())?,
+            Some('b') => base32_decode(chars.as_str())?,
+            Some('B') => base32_decode(&chars.as_str().to_lowercase())?,
+            Some('f') | Some('F') => {
+                crate::tools::hex::decode(chars.as_str()).ok_or(Error::InvalidCid)?
+            }
+            _ => return Err(Error::InvalidCid),
+        };
+        let (version, data) = read_varint(&bytes)?;
+        if version != 1 {
+            return Err(Error::InvalidCid);
+        }
+        let (codec, data) = read_varint(data)?;
+        Ok(ContentId {
+            version: 1,
+            codec,
+            hash: Multihash::from_bytes(data)?,
+        })
+    }
+
+    /// Compute CIDv0 of file data, the same as Drive does.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use xpx_supercontracts_sdk::cid::ContentId;
+    /// let cid = ContentId::compute(b"");
+    /// assert_eq!(cid.to_string(), "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH");
+    /// ```
+    pub fn compute(data: &[u8]) -> Self {
+        ContentId {
+            version: 0,
+            codec: CODEC_DAG_PB,
+            hash: Multihash::sha2_256(&build_dag(data)),
+        }
+    }
+
+    /// Compute CIDv1 of data with `raw` codec.
+    pub fn compute_raw(data: &[u8]) -> Self {
+        ContentId {
+            version: 1,
+            codec: CODEC_RAW,
+            hash: Multihash::sha2_256(data),
+        }
+    }
+
+    /// Check is data match CID. Only `sha2-256` CIDs
+    /// with `dag-pb` or `raw` codec can be verified.
+    pub fn verify(&self, data: &[u8]) -> Result<bool> {
+        if self.hash.code != HASH_SHA2_256 {
+            return Err(Error::InvalidCid);
+        }
+        let hash = match self.codec {
+            CODEC_DAG_PB => Multihash::sha2_256(&build_dag(data)),
+            CODEC_RAW => Multihash::sha2_256(data),
+            _ => return Err(Error::InvalidCid),
+        };
+        Ok(hash == self.hash)
+    }
+
+    /// Binary representation of CID
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.version == 0 {
+            return self.hash.to_bytes();
+        }
+        let mut result = vec![];
+        write_varint(&mut result, u64::from(self.version));
+        write_varint(&mut result, self.codec);
+        result.extend(self.hash.to_bytes());
+        result
+    }
+}
+
+impl std::fmt::Display for ContentId {
+    /// CIDv0 formatted as base58btc, CIDv1 as base32
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.version == 0 {
+            write!(f, "{}", base58_encode(&self.to_bytes()))
+        } else {
+            write!(f, "b{}", base32_encode(&self.to_bytes()))
+        }
+    }
+}
+
+/// Check is data match the given CID string.
+///
+/// ## Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::cid::verify;
+/// assert!(verify(b"", "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH").unwrap());
+/// ```
+pub fn verify(data: &[u8], expected: &str) -> Result<bool> {
+    ContentId::parse(expected)?.verify(data)
+}
+
+/// Read file from Storage via `storage_get` and check
+/// is it match the given CID string. Return file data
+/// only if it match, otherwise `Error::InvalidCid`.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::cid::verified_storage_get;
+/// let data = verified_storage_get(
+///     &"input.json".to_string(),
+///     "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH",
+/// );
+/// if data.is_err() {
+///     // File was changed
+/// }
+/// ```
+pub fn verified_storage_get(file_name: &String, expected: &str) -> Result<Vec<u8>> {
+    let data = storage_get(file_name)?;
+    if !verify(&data, expected)? {
+        return Err(Error::InvalidCid);
+    }
+    Ok(data)
+}
+
+/// DAG node with its total size and file data size
+struct DagNode {
+    hash: Vec<u8>,
+    total_size: u64,
+    file_size: u64,
+}
+
+/// Build balanced UnixFS DAG and return serialized root node
+fn build_dag(data: &[u8]) -> Vec<u8> {
+    if data.len() <= CHUNK_SIZE {
+        return leaf_node(data);
+    }
+    let mut level: Vec<DagNode> = data
+        .chunks(CHUNK_SIZE)
+        .map(|chunk| {
+            let node = leaf_node(chunk);
+            DagNode {
+                hash: Multihash::sha2_256(&node).to_bytes(),
+                total_size: node.len() as u64,
+                file_size: chunk.len() as u64,
+            }
+        })
+        .collect();
+    loop {
+        let mut parents = vec![];
+        for children in level.chunks(MAX_LINKS) {
+            let node = internal_node(children);
+            if level.len() <= MAX_LINKS {
+                return node;
+            }
+            parents.push(DagNode {
+                hash: Multihash::sha2_256(&node).to_bytes(),
+                total_size: node.len() as u64
+                    + children.iter().map(|c| c.total_size).sum::<u64>(),
+                file_size: children.iter().map(|c| c.file_size).sum(),
+            });
+        }
+        level = parents;
+    }
+}
+
+/// UnixFS file type
+const UNIXFS_FILE: u64 = 2;
+
+fn leaf_node(chunk: &[u8]) -> Vec<u8> {
+    let mut unixfs = vec![];
+    write_varint_field(&mut unixfs, 1, UNIXFS_FILE);
+    if !chunk.is_empty() {
+        write_bytes_field(&mut unixfs, 2, chunk);
+    }
+    write_varint_field(&mut unixfs, 3, chunk.len() as u64);
+
+    let mut node = vec![];
+    write_bytes_field(&mut node, 1, &unixfs);
+    node
+}
+
+fn internal_node(children: &[DagNode]) -> Vec<u8> {
+    let mut unixfs = vec![];
+    write_varint_field(&mut unixfs, 1, UNIXFS_FILE);
+    write_varint_field(
+        &mut unixfs,
+        3,
+        children.iter().map(|c| c.file_size).sum::<u64>(),
+    );
+    for child in children {
+        write_varint_field(&mut unixfs, 4, child.file_size);
+    }
+
+    let mut node = vec![];
+    for child in children {
+        let mut link = vec![];
+        write_bytes_field(&mut link, 1, &child.hash);
+        write_bytes_field(&mut link, 2, &[]);
+        write_varint_field(&mut link, 3, child.total_size);
+        write_bytes_field(&mut node, 2, &link);
+    }
+    write_bytes_field(&mut node, 1, &unixfs);
+    node
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn read_varint(data: &[u8]) -> Result<(u64, &[u8])> {
+    let mut value: u64 = 0;
+    for (i, byte) in data.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &data[i + 1..]));
+        }
+    }
+    Err(Error::InvalidCid)
+}
+
+fn write_varint_field(buf: &mut Vec<u8>, field: u64, value: u64) {
+    write_varint(buf, field << 3);
+    write_varint(buf, value);
+}
+
+fn write_bytes_field(buf: &mut Vec<u8>, field: u64, value: &[u8]) {
+    write_varint(buf, field << 3 | 2);
+    write_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value);
+}
+
+fn base58_encode(data: &[u8]) -> String {
+    let mut digits: Vec<u8> = vec![];
+    for byte in data {
+        let mut carry = u32::from(*byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = data.iter().take_while(|b| **b == 0).count();
+    std::iter::repeat('1')
+        .take(zeros)
+        .chain(digits.iter().rev().map(|d| BASE58_ALPHABET[*d as usize] as char))
+        .collect()
+}
+
+fn base58_decode(data: &str) -> Result<Vec<u8>> {
+    let mut bytes: Vec<u8> = vec![];
+    for c in data.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|a| *a == c)
+            .ok_or(Error::InvalidCid)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = data.bytes().take_while(|c| *c == b'1').count();
+    let mut result = vec![0; zeros];
+    result.extend(bytes.iter().rev());
+    Ok(result)
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut result = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in data {
+        buffer = buffer << 8 | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            result.push(BASE32_ALPHABET[(buffer >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        result.push(BASE32_ALPHABET[(buffer << (5 - bits) & 0x1f) as usize] as char);
+    }
+    result
+}
+
+fn base32_decode(data: &str) -> Result<Vec<u8>> {
+    let mut result = vec![];
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in data.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|a| *a == c)
+            .ok_or(Error::InvalidCid)? as u32;
+        buffer = buffer << 5 | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY: &str = "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH";
+    const HELLO: &str = "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o";
+
+    #[test]
+    fn compute_matches_ipfs_add() {
+        assert_eq!(ContentId::compute(b"").to_string(), EMPTY);
+        assert_eq!(ContentId::compute(b"hello world\n").to_string(), HELLO);
+    }
+
+    #[test]
+    fn cid_v0_round_trip() {
+        let cid = ContentId::parse(HELLO).unwrap();
+        assert_eq!(cid.version, 0);
+        assert_eq!(cid.codec, CODEC_DAG_PB);
+        assert_eq!(cid.to_string(), HELLO);
+        assert!(cid.verify(b"hello world\n").unwrap());
+        assert!(!cid.verify(b"hello world").unwrap());
+    }
+
+    #[test]
+    fn cid_v1_multibases_parsed() {
+        let cid = ContentId::compute_raw(b"hello world");
+        let base32 = cid.to_string();
+        assert!(base32.starts_with('b'));
+        let base16 = format!("f{}", crate::tools::hex::encode(&cid.to_bytes()));
+        let base58 = format!("z{}", base58_encode(&cid.to_bytes()));
+        for text in [base32.clone(), base32.to_uppercase(), base16, base58].iter() {
+            assert_eq!(ContentId::parse(text).unwrap(), cid);
+        }
+        assert!(verify(b"hello world", &base32).unwrap());
+    }
+
+    #[test]
+    fn multi_chunk_file_verified() {
+        let data: Vec<u8> = (0..CHUNK_SIZE * 3 + 1).map(|i| (i % 251) as u8).collect();
+        let cid = ContentId::compute(&data);
+        assert_ne!(cid, ContentId::compute(&data[..CHUNK_SIZE * 3]));
+        assert!(verify(&data, &cid.to_string()).unwrap());
+    }
+
+    #[test]
+    fn invalid_cid_rejected() {
+        assert!(ContentId::parse("").is_err());
+        assert!(ContentId::parse("Qm0000000000000000000000000000000000000000000").is_err());
+        assert!(ContentId::parse("xabc").is_err());
+        assert!(ContentId::parse("fzz").is_err());
+        let mut bytes = ContentId::compute_raw(b"").to_bytes();
+        bytes[0] = 2;
+        assert!(ContentId::parse(&format!("f{}", crate::tools::hex::encode(&bytes))).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! # Drive file system functions
+//!
+//! Functionality to discover and manage files on SuperContract Drive.
+//! Complements `storage` module.
+//!
+//! All paths located inside `/root/supercontracts/` directory.
+//! So it should be relative path like: `path/to/my/file.json`.
+//! Absolute paths and `..` segments are rejected before
+//! any call to `WasmVM`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::external;
+use crate::statuses::{Error, FunctionResult, Result};
+use crate::tools::call::{call_external_func, simple_call_external_func};
+use crate::transactions_type::Cid;
+
+/// Drive path params for `WasmVM`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct DrivePath {
+    path: String,
+}
+
+/// Rename params for `WasmVM`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct DriveRename {
+    from: String,
+    to: String,
+}
+
+/// File or directory metadata
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct FileMetadata {
+    /// Relative path inside `/root/supercontracts/`
+    pub path: String,
+    /// File size in bytes. Zero for directories
+    pub size: u64,
+    /// Content ID of the file. `None` for directories
+    pub cid: Option<Cid>,
+    pub is_dir: bool,
+}
+
+/// Validate path for Drive sandbox. Path should be relative
+/// and shouldn't contain `..` segments.
+///
+/// ## Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::drive::validate_path;
+/// assert!(validate_path("input/data.csv").is_ok());
+/// assert!(validate_path("/etc/passwd").is_err());
+/// assert!(validate_path("input/../../secret").is_err());
+/// ```
+pub fn validate_path(path: &str) -> Result<()> {
+    if path.is_empty() || path.starts_with('/') || path.starts_with('\\') {
+        return Err(Error::InvalidPath);
+    }
+    if path.split(['/', '\\']).any(|segment| segment == "..") {
+        return Err(Error::InvalidPath);
+    }
+    Ok(())
+}
+
+fn drive_path(path: &str) -> Result<DrivePath> {
+    validate_path(path)?;
+    Ok(DrivePath {
+        path: path.to_string(),
+    })
+}
+
+/// List directory entries. To list root directory
+/// use `"."` as path.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::drive::list_dir;
+/// let entries = list_dir("input").unwrap();
+/// for entry in entries.iter().filter(|e| !e.is_dir) {
+///     // Process input file
+/// }
+/// ```
+pub fn list_dir(path: &str) -> Result<Vec<FileMetadata>> {
+    let params = drive_path(path)?;
+    let entries: Option<Vec<FileMetadata>> =
+        call_external_func(&params, external::drive_list_dir)?;
+    Ok(entries.unwrap_or_default())
+}
+
+/// Get file or directory metadata. Return `None`
+/// if path doesn't exist.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::drive::metadata;
+/// if let Some(meta) = metadata("input/data.csv").unwrap() {
+///     let size = meta.size;
+/// }
+/// ```
+pub fn metadata(path: &str) -> Result<Option<FileMetadata>> {
+    let params = drive_path(path)?;
+    call_external_func(&params, external::drive_file_metadata)
+}
+
+/// Check is file or directory exist.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::drive::exists;
+/// if exists("input/data.csv").unwrap() {
+///     // Process file
+/// }
+/// ```
+pub fn exists(path: &str) -> Result<bool> {
+    Ok(metadata(path)?.is_some())
+}
+
+/// Remove file or empty directory. Return result status.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::drive::remove;
+/// let status = remove("input/data.csv");
+/// assert_eq!(status.unwrap(), 0);
+/// ```
+pub fn remove(path: &str) -> FunctionResult {
+    let params = drive_path(path)?;
+    simple_call_external_func(&params, external::drive_remove)
+}
+
+/// Rename or move file or directory. Return result status.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::drive::rename;
+/// let status = rename("input/data.csv", "processed/data.csv");
+/// assert_eq!(status.unwrap(), 0);
+/// ```
+pub fn rename(from: &str, to: &str) -> FunctionResult {
+    validate_path(from)?;
+    validate_path(to)?;
+    let params = DriveRename {
+        from: from.to_string(),
+        to: to.to_string(),
+    };
+    simple_call_external_func(&params, external::drive_rename)
+}
+
+/// Create directory with all parent directories.
+/// Return result status.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::drive::create_dir;
+/// let status = create_dir("output/2019");
+/// assert_eq!(status.unwrap(), 0);
+/// ```
+pub fn create_dir(path: &str) -> FunctionResult {
+    let params = drive_path(path)?;
+    simple_call_external_func(&params, external::drive_create_dir)
+}
+
+/// Append data to the end of file. If file not exist
+/// it will be created. Return result status.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::drive::append;
+/// let status = append("output/log.txt", "line\n".as_bytes());
+/// assert_eq!(status.unwrap(), 0);
+/// ```
+pub fn append(path: &str, data: &[u8]) -> FunctionResult {
+    validate_path(path)?;
+    let path = path.as_bytes();
+    unsafe {
+        Ok(external::drive_append(
+            path.as_ptr(),
+            path.len(),
+            data.as_ptr(),
+            data.len(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_paths_allowed() {
+        assert!(validate_path(".").is_ok());
+        assert!(validate_path("input/data.csv").is_ok());
+        assert!(validate_path("input/..data/file..csv").is_ok());
+    }
+
+    #[test]
+    fn escaping_paths_rejected() {
+        assert!(validate_path("").is_err());
+        assert!(validate_path("/etc/passwd").is_err());
+        assert!(validate_path("\\server\\share").is_err());
+        assert!(validate_path("..").is_err());
+        assert!(validate_path("input/../../secret").is_err());
+        assert!(validate_path("input\\..\\secret").is_err());
+    }
+}
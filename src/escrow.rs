@@ -0,0 +1,589 @@
+//! # Escrow
+//!
+//! Escrow hold mosaics of depositor on SuperContract account
+//! until release condition met, then transfer them to beneficiary.
+//! If condition not met until refund height, mosaics returned
+//! to depositor. Any party can open dispute, and arbiter decide
+//! who receive mosaics. If arbiter doesn't resolve dispute until
+//! refund height, mosaics can be returned to depositor.
+//!
+//! Escrow is state machine:
+//! ```ignore
+//! Open --deposit--> Funded --release--> Released
+//!                     |  \--refund---> Refunded
+//!                     \--dispute--> Disputed --resolve--> Released | Refunded
+//!                                          \--refund---> Refunded
+//! ```
+//! Every operation check current state and initiator of
+//! Execute transaction, and return `Error::InvalidEscrowState`
+//! or `Error::NotEscrowParty` without changes if not allowed.
+//!
+//! Deposit verified against mosaics of Execute transaction,
+//! and escrow mosaics paid out with single **Transfer transaction**,
+//! so payout is either sent completely or not sent at all.
+//!
+//! Escrow state persisted in Storage file `escrow/<id>.json`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::statuses::{check_status, Error, Result};
+use crate::storage::{save_result, storage_get};
+use crate::transactions::{get_execution_context, transfer_mosaics};
+use crate::transactions_type::{
+    Amount, AssetId, Height, Mosaic, PubKey, Recipient, TransferMosaics,
+};
+
+/// Condition to release mosaics to beneficiary
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ReleaseCondition {
+    /// Release at Blockchain height
+    Timeout { height: Height },
+    /// Release when `threshold` of `approvers` approved
+    Approvals {
+        approvers: BTreeSet<PubKey>,
+        threshold: usize,
+    },
+    /// Release when `oracle` reported `expected` value
+    Oracle { oracle: PubKey, expected: String },
+}
+
+/// Terms of escrow agreement
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EscrowTerms {
+    pub depositor: PubKey,
+    pub beneficiary: PubKey,
+    pub mosaics: Vec<Mosaic>,
+    pub condition: ReleaseCondition,
+    /// Height after that depositor can refund mosaics
+    /// if condition not met or dispute not resolved
+    pub refund_height: Height,
+    /// Party that resolve disputes. Without arbiter
+    /// disputes not allowed.
+    pub arbiter: Option<PubKey>,
+}
+
+/// State of escrow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum EscrowState {
+    /// Waiting deposit
+    Open,
+    /// Mosaics deposited, waiting release condition
+    Funded,
+    /// Dispute opened, waiting arbiter decision
+    Disputed,
+    /// Mosaics transferred to beneficiary
+    Released,
+    /// Mosaics returned to depositor
+    Refunded,
+}
+
+/// Escrow agreement
+///
+/// # Examples
+/// ```rust,no_run
+/// use std::collections::{BTreeMap, BTreeSet};
+/// use xpx_supercontracts_sdk::escrow::{Escrow, EscrowTerms, ReleaseCondition};
+/// use xpx_supercontracts_sdk::transactions_type::Mosaic;
+///
+/// let buyer = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string();
+/// let seller = "F8A5C2B6D9E74B1A9C0D3E2F1A4B5C6D7E8F9A0B1C2D3E4F5A6B7C8D9E0F1A2B".to_string();
+///
+/// // Buyer open and fund escrow, Execute transaction carry mosaics
+/// let mut escrow = Escrow::open("order-42", EscrowTerms {
+///     depositor: buyer.clone(),
+///     beneficiary: seller,
+///     mosaics: vec![Mosaic { asset_id: 992621222383397347, amount: 1000 }],
+///     condition: ReleaseCondition::Approvals {
+///         approvers: vec![buyer].into_iter().collect::<BTreeSet<_>>(),
+///         threshold: 1,
+///     },
+///     refund_height: 100_000,
+///     arbiter: None,
+/// })
+/// .unwrap();
+/// let _ = escrow.deposit();
+///
+/// // Buyer approve in the next execution, mosaics released to seller
+/// let mut escrow = Escrow::load("order-42").unwrap().unwrap();
+/// let _ = escrow.approve();
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Escrow {
+    pub id: String,
+    pub terms: EscrowTerms,
+    pub state: EscrowState,
+    pub approvals: BTreeSet<PubKey>,
+    pub oracle_value: Option<String>,
+}
+
+impl Escrow {
+    /// Create escrow and save it. Return `Error::InvalidEscrowState`
+    /// if escrow with the same id already exist, and
+    /// `Error::InvalidAmount` if mosaics empty or not positive.
+    pub fn open(id: &str, mut terms: EscrowTerms) -> Result<Escrow> {
+        if Escrow::load(id)?.is_some() {
+            return Err(Error::InvalidEscrowState);
+        }
+        validate_terms(&mut terms)?;
+        let escrow = Escrow {
+            id: id.to_string(),
+            terms,
+            state: EscrowState::Open,
+            approvals: BTreeSet::new(),
+            oracle_value: None,
+        };
+        escrow.save()?;
+        Ok(escrow)
+    }
+
+    /// Escrow file name in Storage
+    pub fn file_name(id: &str) -> String {
+        format!("escrow/{}.json", id)
+    }
+
+    /// Load escrow. Return `None` if escrow not exist.
+    pub fn load(id: &str) -> Result<Option<Escrow>> {
+        let data = storage_get(&Escrow::file_name(id))?;
+        if data.is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(|_| Error::DeserializeJson)
+    }
+
+    fn save(&self) -> Result<()> {
+        let data = serde_json::to_vec(self).map_err(|_| Error::SerializeJson)?;
+        check_status(save_result(&Escrow::file_name(&self.id), &data)?)?;
+        Ok(())
+    }
+
+    fn require_state(&self, state: EscrowState) -> Result<()> {
+        if self.state != state {
+            return Err(Error::InvalidEscrowState);
+        }
+        Ok(())
+    }
+
+    /// Record deposit. Should be invoked by depositor with
+    /// Execute transaction that transfer escrow mosaics
+    /// to SuperContract account. Return `Error::InsufficientDeposit`
+    /// if Execute transaction mosaics less than escrow mosaics.
+    pub fn deposit(&mut self) -> Result<()> {
+        let ctx = get_execution_context()?;
+        self.check_deposit(&normalize(&ctx.initiator), &ctx.mosaics)?;
+        self.state = EscrowState::Funded;
+        self.save()
+    }
+
+    fn check_deposit(&self, initiator: &str, mosaics: &[Mosaic]) -> Result<()> {
+        self.require_state(EscrowState::Open)?;
+        if initiator != self.terms.depositor {
+            return Err(Error::NotEscrowParty);
+        }
+        let deposited = sum_mosaics(mosaics);
+        let covered = sum_mosaics(&self.terms.mosaics)
+            .iter()
+            .all(|(asset_id, amount)| deposited.get(asset_id).is_some_and(|paid| paid >= amount));
+        if !covered {
+            return Err(Error::InsufficientDeposit);
+        }
+        Ok(())
+    }
+
+    /// Approve release by initiator. Mosaics released
+    /// immediately when threshold reached.
+    pub fn approve(&mut self) -> Result<EscrowState> {
+        let initiator = initiator()?;
+        self.require_state(EscrowState::Funded)?;
+        match &self.terms.condition {
+            ReleaseCondition::Approvals { approvers, .. } if approvers.contains(&initiator) => {}
+            _ => return Err(Error::NotEscrowParty),
+        }
+        self.approvals.insert(initiator);
+        self.settle_if_ready()
+    }
+
+    /// Report oracle value. Mosaics released immediately
+    /// when value equal to expected.
+    pub fn report(&mut self, value: &str) -> Result<EscrowState> {
+        let initiator = initiator()?;
+        self.require_state(EscrowState::Funded)?;
+        match &self.terms.condition {
+            ReleaseCondition::Oracle { oracle, .. } if *oracle == initiator => {}
+            _ => return Err(Error::NotEscrowParty),
+        }
+        self.oracle_value = Some(value.to_string());
+        self.settle_if_ready()
+    }
+
+    /// Is release condition met at the given Blockchain `height`
+    pub fn is_release_ready(&self, height: Height) -> bool {
+        match &self.terms.condition {
+            ReleaseCondition::Timeout { height: release } => height >= *release,
+            _ => self.is_approved(),
+        }
+    }
+
+    /// Is approvals or oracle condition met. Doesn't depend on height.
+    fn is_approved(&self) -> bool {
+        match &self.terms.condition {
+            ReleaseCondition::Timeout { .. } => false,
+            ReleaseCondition::Approvals { threshold, .. } => self.approvals.len() >= *threshold,
+            ReleaseCondition::Oracle { expected, .. } => {
+                self.oracle_value.as_ref() == Some(expected)
+            }
+        }
+    }
+
+    /// Is refund allowed at the given Blockchain `height`.
+    /// Disputed escrow can be refunded at refund height
+    /// regardless of release condition.
+    pub fn is_refund_ready(&self, height: Height) -> bool {
+        match self.state {
+            EscrowState::Disputed => height >= self.terms.refund_height,
+            _ => height >= self.terms.refund_height && !self.is_release_ready(height),
+        }
+    }
+
+    /// Release mosaics to beneficiary if condition met.
+    /// Can be invoked by anyone.
+    pub fn release(&mut self) -> Result<()> {
+        let ctx = get_execution_context()?;
+        self.check_release(ctx.height.ok_or(Error::UnknownHeight)?)?;
+        self.settle(EscrowState::Released)
+    }
+
+    fn check_release(&self, height: Height) -> Result<()> {
+        self.require_state(EscrowState::Funded)?;
+        if !self.is_release_ready(height) {
+            return Err(Error::InvalidEscrowState);
+        }
+        Ok(())
+    }
+
+    /// Return mosaics to depositor if condition not met or
+    /// dispute not resolved until refund height.
+    /// Can be invoked by anyone.
+    pub fn refund(&mut self) -> Result<()> {
+        let ctx = get_execution_context()?;
+        self.check_refund(ctx.height.ok_or(Error::UnknownHeight)?)?;
+        self.settle(EscrowState::Refunded)
+    }
+
+    fn check_refund(&self, height: Height) -> Result<()> {
+        if self.state != EscrowState::Funded && self.state != EscrowState::Disputed {
+            return Err(Error::InvalidEscrowState);
+        }
+        if !self.is_refund_ready(height) {
+            return Err(Error::InvalidEscrowState);
+        }
+        Ok(())
+    }
+
+    /// Open dispute. Can be invoked by depositor or beneficiary
+    /// if escrow has arbiter.
+    pub fn dispute(&mut self) -> Result<()> {
+        self.check_dispute(&initiator()?)?;
+        self.state = EscrowState::Disputed;
+        self.save()
+    }
+
+    fn check_dispute(&self, initiator: &str) -> Result<()> {
+        self.require_state(EscrowState::Funded)?;
+        if self.terms.arbiter.is_none() {
+            return Err(Error::InvalidEscrowState);
+        }
+        if initiator != self.terms.depositor && initiator != self.terms.beneficiary {
+            return Err(Error::NotEscrowParty);
+        }
+        Ok(())
+    }
+
+    /// Resolve dispute by arbiter: release mosaics to
+    /// beneficiary or refund them to depositor.
+    pub fn resolve(&mut self, release: bool) -> Result<()> {
+        self.check_resolve(&initiator()?)?;
+        if release {
+            self.settle(EscrowState::Released)
+        } else {
+            self.settle(EscrowState::Refunded)
+        }
+    }
+
+    fn check_resolve(&self, initiator: &str) -> Result<()> {
+        self.require_state(EscrowState::Disputed)?;
+        if self.terms.arbiter.as_deref() != Some(initiator) {
+            return Err(Error::NotEscrowParty);
+        }
+        Ok(())
+    }
+
+    fn settle_if_ready(&mut self) -> Result<EscrowState> {
+        if self.is_approved() {
+            self.settle(EscrowState::Released)?;
+        } else {
+            self.save()?;
+        }
+        Ok(self.state)
+    }
+
+    /// Send to transaction pool single **Transfer transaction**
+    /// with all escrow mosaics and move escrow to final state.
+    /// If transaction not accepted, escrow state not changed
+    /// and settlement can be retried.
+    fn settle(&mut self, state: EscrowState) -> Result<()> {
+        let recipient = match state {
+            EscrowState::Released => &self.terms.beneficiary,
+            EscrowState::Refunded => &self.terms.depositor,
+            _ => return Err(Error::InvalidEscrowState),
+        };
+        check_status(transfer_mosaics(&TransferMosaics {
+            recipient: Recipient::PubKey(recipient.clone()),
+            mosaics: self.terms.mosaics.clone(),
+            message: None,
+        })?)?;
+        self.state = state;
+        self.save()
+    }
+}
+
+/// Validate terms and normalize public keys. Return
+/// `Error::InvalidAmount` if mosaics empty or not positive, and
+/// `Error::InvalidEscrowState` if approvals threshold can't be reached.
+fn validate_terms(terms: &mut EscrowTerms) -> Result<()> {
+    if terms.mosaics.is_empty() || terms.mosaics.iter().any(|mosaic| mosaic.amount <= 0) {
+        return Err(Error::InvalidAmount);
+    }
+    terms.depositor = normalize(&terms.depositor);
+    terms.beneficiary = normalize(&terms.beneficiary);
+    terms.arbiter = terms.arbiter.as_ref().map(|arbiter| normalize(arbiter));
+    match &mut terms.condition {
+        ReleaseCondition::Approvals {
+            approvers,
+            threshold,
+        } => {
+            *approvers = approvers.iter().map(|key| normalize(key)).collect();
+            if *threshold == 0 || *threshold > approvers.len() {
+                return Err(Error::InvalidEscrowState);
+            }
+        }
+        ReleaseCondition::Oracle { oracle, .. } => *oracle = normalize(oracle),
+        ReleaseCondition::Timeout { .. } => {}
+    }
+    Ok(())
+}
+
+fn normalize(pub_key: &str) -> PubKey {
+    pub_key.to_uppercase()
+}
+
+fn sum_mosaics(mosaics: &[Mosaic]) -> BTreeMap<AssetId, Amount> {
+    let mut sum = BTreeMap::new();
+    for mosaic in mosaics {
+        let amount = sum.entry(mosaic.asset_id).or_insert(0);
+        *amount = mosaic.amount.saturating_add(*amount);
+    }
+    sum
+}
+
+fn initiator() -> Result<PubKey> {
+    Ok(normalize(&get_execution_context()?.initiator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPOSITOR: &str = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C";
+    const BENEFICIARY: &str = "F8A5C2B6D9E74B1A9C0D3E2F1A4B5C6D7E8F9A0B1C2D3E4F5A6B7C8D9E0F1A2B";
+    const ARBITER: &str = "A1B2C3D4E5F60718293A4B5C6D7E8F90A1B2C3D4E5F60718293A4B5C6D7E8F90";
+
+    fn mosaic(asset_id: AssetId, amount: Amount) -> Mosaic {
+        Mosaic { asset_id, amount }
+    }
+
+    fn terms(condition: ReleaseCondition) -> EscrowTerms {
+        EscrowTerms {
+            depositor: DEPOSITOR.to_lowercase(),
+            beneficiary: BENEFICIARY.to_string(),
+            mosaics: vec![mosaic(1, 100), mosaic(2, 5)],
+            condition,
+            refund_height: 1000,
+            arbiter: Some(ARBITER.to_lowercase()),
+        }
+    }
+
+    fn escrow(condition: ReleaseCondition, state: EscrowState) -> Escrow {
+        let mut terms = terms(condition);
+        validate_terms(&mut terms).unwrap();
+        Escrow {
+            id: "order-42".to_string(),
+            terms,
+            state,
+            approvals: BTreeSet::new(),
+            oracle_value: None,
+        }
+    }
+
+    fn approvals(threshold: usize) -> ReleaseCondition {
+        ReleaseCondition::Approvals {
+            approvers: vec![DEPOSITOR.to_lowercase(), ARBITER.to_string()]
+                .into_iter()
+                .collect(),
+            threshold,
+        }
+    }
+
+    #[test]
+    fn terms_validated_and_normalized() {
+        let mut valid = terms(approvals(2));
+        validate_terms(&mut valid).unwrap();
+        assert_eq!(valid.depositor, DEPOSITOR);
+        assert_eq!(valid.arbiter.as_deref(), Some(ARBITER));
+        match valid.condition {
+            ReleaseCondition::Approvals { approvers, .. } => {
+                assert!(approvers.contains(DEPOSITOR));
+            }
+            _ => unreachable!(),
+        }
+
+        for threshold in [0, 3].iter() {
+            assert!(matches!(
+                validate_terms(&mut terms(approvals(*threshold))),
+                Err(Error::InvalidEscrowState)
+            ));
+        }
+        for mosaics in [vec![], vec![mosaic(1, 100), mosaic(2, 0)]].iter() {
+            let mut invalid = terms(ReleaseCondition::Timeout { height: 500 });
+            invalid.mosaics = mosaics.clone();
+            assert!(matches!(
+                validate_terms(&mut invalid),
+                Err(Error::InvalidAmount)
+            ));
+        }
+    }
+
+    #[test]
+    fn release_ready_by_condition() {
+        let timeout = escrow(
+            ReleaseCondition::Timeout { height: 500 },
+            EscrowState::Funded,
+        );
+        assert!(!timeout.is_release_ready(499));
+        assert!(timeout.is_release_ready(500));
+
+        let mut approved = escrow(approvals(2), EscrowState::Funded);
+        approved.approvals.insert(DEPOSITOR.to_string());
+        assert!(!approved.is_release_ready(Height::MAX));
+        approved.approvals.insert(ARBITER.to_string());
+        assert!(approved.is_release_ready(0));
+
+        let oracle = ReleaseCondition::Oracle {
+            oracle: ARBITER.to_string(),
+            expected: "delivered".to_string(),
+        };
+        let mut reported = escrow(oracle, EscrowState::Funded);
+        reported.oracle_value = Some("shipped".to_string());
+        assert!(!reported.is_release_ready(0));
+        reported.oracle_value = Some("delivered".to_string());
+        assert!(reported.is_release_ready(0));
+    }
+
+    #[test]
+    fn refund_ready_after_refund_height_if_not_released() {
+        let timeout = escrow(
+            ReleaseCondition::Timeout { height: 2000 },
+            EscrowState::Funded,
+        );
+        assert!(!timeout.is_refund_ready(999));
+        assert!(timeout.is_refund_ready(1000));
+        assert!(!timeout.is_refund_ready(2000));
+
+        let mut approved = escrow(approvals(1), EscrowState::Funded);
+        approved.approvals.insert(ARBITER.to_string());
+        assert!(!approved.is_refund_ready(1000));
+
+        // Dispute blocks release, so only refund height matters
+        approved.state = EscrowState::Disputed;
+        assert!(!approved.is_refund_ready(999));
+        assert!(approved.is_refund_ready(1000));
+    }
+
+    #[test]
+    fn transitions_guarded_by_state() {
+        let timeout = ReleaseCondition::Timeout { height: 500 };
+        let funded = escrow(timeout.clone(), EscrowState::Funded);
+        assert!(funded.check_release(500).is_ok());
+        assert!(funded.check_refund(1000).is_err());
+        assert!(matches!(
+            funded.check_deposit(DEPOSITOR, &funded.terms.mosaics),
+            Err(Error::InvalidEscrowState)
+        ));
+        assert!(funded.check_resolve(ARBITER).is_err());
+
+        let disputed = escrow(timeout.clone(), EscrowState::Disputed);
+        assert!(disputed.check_release(500).is_err());
+        assert!(disputed.check_refund(999).is_err());
+        assert!(disputed.check_refund(1000).is_ok());
+        assert!(disputed.check_dispute(DEPOSITOR).is_err());
+
+        for state in [
+            EscrowState::Open,
+            EscrowState::Released,
+            EscrowState::Refunded,
+        ]
+        .iter()
+        {
+            let escrow = escrow(timeout.clone(), *state);
+            assert!(escrow.check_release(Height::MAX).is_err());
+            assert!(escrow.check_refund(Height::MAX).is_err());
+            assert!(escrow.check_dispute(DEPOSITOR).is_err());
+            assert!(escrow.check_resolve(ARBITER).is_err());
+        }
+    }
+
+    #[test]
+    fn deposit_checked_against_mosaics() {
+        let open = escrow(approvals(1), EscrowState::Open);
+        let paid = vec![mosaic(1, 60), mosaic(2, 5), mosaic(1, 40)];
+        assert!(open.check_deposit(DEPOSITOR, &paid).is_ok());
+        assert!(matches!(
+            open.check_deposit(BENEFICIARY, &paid),
+            Err(Error::NotEscrowParty)
+        ));
+        assert!(matches!(
+            open.check_deposit(DEPOSITOR, &paid[..2]),
+            Err(Error::InsufficientDeposit)
+        ));
+    }
+
+    #[test]
+    fn dispute_and_resolve_parties() {
+        let funded = escrow(approvals(1), EscrowState::Funded);
+        assert!(funded.check_dispute(DEPOSITOR).is_ok());
+        assert!(funded.check_dispute(BENEFICIARY).is_ok());
+        assert!(matches!(
+            funded.check_dispute(ARBITER),
+            Err(Error::NotEscrowParty)
+        ));
+
+        let mut no_arbiter = funded.clone();
+        no_arbiter.terms.arbiter = None;
+        assert!(matches!(
+            no_arbiter.check_dispute(DEPOSITOR),
+            Err(Error::InvalidEscrowState)
+        ));
+
+        let disputed = escrow(approvals(1), EscrowState::Disputed);
+        assert!(disputed.check_resolve(ARBITER).is_ok());
+        assert!(matches!(
+            disputed.check_resolve(DEPOSITOR),
+            Err(Error::NotEscrowParty)
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+//! # Exchange helpers
+//!
+//! Price math and order book matching for exchange offers.
+//! Order book can be fetched via `transactions::get_exchange_offers`,
+//! and matched offers sent via `transactions::exchange_offer`.
+//!
+//! Offer price is `price_numerator / price_denominator` - cost
+//! of one mosaic unit. All calculations are in integers,
+//! so all Executors get the same result.
+
+use std::cmp::Ordering;
+
+use crate::transactions_type::{
+    Amount, ExchangeConfirmation, ExchangeOffer, Mosaic, OfferInfo, OfferType, BUY_OFFER,
+};
+
+/// Calculate cost of `amount` mosaic units for specific offer.
+/// Cost rounded up. Return `None` for offer with invalid price
+/// or if cost overflow `Amount`.
+///
+/// ## Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::exchange::offer_cost;
+/// use xpx_supercontracts_sdk::transactions_type::{OfferInfo, SELL_OFFER};
+///
+/// let offer = OfferInfo {
+///     offer_type: SELL_OFFER,
+///     owner: None,
+///     mosaic: None,
+///     price_numerator: 3,
+///     price_denominator: 2,
+///     deadline: 1000,
+/// };
+/// assert_eq!(offer_cost(&offer, 10), Some(15));
+/// ```
+pub fn offer_cost(offer: &OfferInfo, amount: Amount) -> Option<Amount> {
+    if offer.price_denominator <= 0 || offer.price_numerator < 0 || amount < 0 {
+        return None;
+    }
+    let numerator = amount as i128 * offer.price_numerator as i128;
+    let denominator = offer.price_denominator as i128;
+    let cost = (numerator + denominator - 1) / denominator;
+    if cost > Amount::MAX as i128 {
+        return None;
+    }
+    Some(cost as Amount)
+}
+
+/// Compare offers prices without precision loss.
+pub fn compare_price(left: &OfferInfo, right: &OfferInfo) -> Ordering {
+    let left_price = left.price_numerator as i128 * right.price_denominator as i128;
+    let right_price = right.price_numerator as i128 * left.price_denominator as i128;
+    left_price.cmp(&right_price)
+}
+
+/// Sort offers of `offer_type` from best to worst for counterparty.
+/// Sell offers sorted from lowest price, buy offers from
+/// highest price. Offers with the same price keep original order.
+/// Offers of other type should be filtered out before sorting.
+pub fn sort_offers(offers: &mut [OfferInfo], offer_type: OfferType) {
+    offers.sort_by(|left, right| {
+        let ord = compare_price(left, right);
+        if offer_type == BUY_OFFER {
+            ord.reverse()
+        } else {
+            ord
+        }
+    });
+}
+
+/// Pick best offers of `offer_type` from order book to exchange
+/// exactly `amount` mosaic units. Result ready for
+/// `transactions::exchange_offer`.
+///
+/// Offers of other type, offers without owner or mosaic,
+/// and offers with invalid price are skipped. Return `None` if order book hasn't enough
+/// liquidity for the given `amount`.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::exchange::match_offers;
+/// use xpx_supercontracts_sdk::transactions::{exchange_offer, get_exchange_offers};
+/// use xpx_supercontracts_sdk::transactions_type::{ExchangeOffer, GetExchangeOffers, SELL_OFFER};
+///
+/// let offers = get_exchange_offers(&GetExchangeOffers {
+///     asset_id: 10,
+///     offer_type: SELL_OFFER,
+/// });
+/// let offers = offers.unwrap().unwrap_or_default();
+/// if let Some(confirmations) = match_offers(&offers, SELL_OFFER, 1000) {
+///     let _ = exchange_offer(&ExchangeOffer {
+///         offer: Some(confirmations),
+///     });
+/// }
+/// ```
+pub fn match_offers(
+    offers: &[OfferInfo],
+    offer_type: OfferType,
+    amount: Amount,
+) -> Option<Vec<ExchangeConfirmation>> {
+    let mut book: Vec<OfferInfo> = offers
+        .iter()
+        .filter(|offer| {
+            offer.offer_type == offer_type
+                && offer.owner.is_some()
+                && offer.price_denominator > 0
+                && offer.price_numerator >= 0
+                && offer.mosaic.as_ref().is_some_and(|m| m.amount > 0)
+        })
+        .cloned()
+        .collect();
+    sort_offers(&mut book, offer_type);
+
+    let mut rest = amount;
+    let mut confirmations = vec![];
+    for offer in book.iter() {
+        if rest <= 0 {
+            break;
+        }
+        let mosaic = offer.mosaic.as_ref()?;
+        let take = rest.min(mosaic.amount);
+        confirmations.push(ExchangeConfirmation {
+            offer_type: offer.offer_type,
+            mosaic: Some(Mosaic {
+                asset_id: mosaic.asset_id,
+                amount: take,
+            }),
+            cost: offer_cost(offer, take)?,
+            owner: offer.owner.clone(),
+        });
+        rest -= take;
+    }
+    if rest > 0 {
+        return None;
+    }
+    Some(confirmations)
+}
+
+/// Build **ExchangeOffer transaction** params for exactly
+/// `amount` mosaic units from offers of `offer_type` in the given order book.
+pub fn exchange_for_amount(
+    offers: &[OfferInfo],
+    offer_type: OfferType,
+    amount: Amount,
+) -> Option<ExchangeOffer> {
+    match_offers(offers, offer_type, amount).map(|confirmations| ExchangeOffer {
+        offer: Some(confirmations),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions_type::{PublicAccount, SELL_OFFER};
+
+    fn buy_offer(owner: &str, amount: Amount, numerator: Amount, denominator: Amount) -> OfferInfo {
+        OfferInfo {
+            offer_type: BUY_OFFER,
+            ..sell_offer(owner, amount, numerator, denominator)
+        }
+    }
+
+    fn sell_offer(
+        owner: &str,
+        amount: Amount,
+        numerator: Amount,
+        denominator: Amount,
+    ) -> OfferInfo {
+        OfferInfo {
+            offer_type: SELL_OFFER,
+            owner: Some(PublicAccount {
+                public_key: owner.to_string(),
+            }),
+            mosaic: Some(Mosaic {
+                asset_id: 10,
+                amount,
+            }),
+            price_numerator: numerator,
+            price_denominator: denominator,
+            deadline: 1000,
+        }
+    }
+
+    fn fills(confirmations: &[ExchangeConfirmation]) -> Vec<(&str, Amount, Amount)> {
+        confirmations
+            .iter()
+            .map(|c| {
+                (
+                    c.owner.as_ref().unwrap().public_key.as_str(),
+                    c.mosaic.as_ref().unwrap().amount,
+                    c.cost,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn offer_cost_rounded_up() {
+        let offer = sell_offer("A", 100, 1, 3);
+        assert_eq!(offer_cost(&offer, 3), Some(1));
+        assert_eq!(offer_cost(&offer, 4), Some(2));
+        assert_eq!(offer_cost(&offer, 0), Some(0));
+    }
+
+    #[test]
+    fn offer_cost_rejects_invalid_price_and_overflow() {
+        assert_eq!(offer_cost(&sell_offer("A", 100, 1, 0), 1), None);
+        assert_eq!(offer_cost(&sell_offer("A", 100, -1, 1), 1), None);
+        assert_eq!(offer_cost(&sell_offer("A", 100, 1, 1), -1), None);
+        assert_eq!(offer_cost(&sell_offer("A", 100, 2, 1), Amount::MAX), None);
+    }
+
+    #[test]
+    fn sell_offers_matched_from_lowest_price() {
+        let offers = vec![
+            sell_offer("EXPENSIVE", 100, 3, 1),
+            sell_offer("CHEAP", 50, 1, 1),
+            sell_offer("NO_PRICE", 100, 1, 0),
+            sell_offer("MIDDLE", 30, 2, 1),
+        ];
+        let confirmations = match_offers(&offers, SELL_OFFER, 100).unwrap();
+        assert_eq!(
+            fills(&confirmations),
+            vec![("CHEAP", 50, 50), ("MIDDLE", 30, 60), ("EXPENSIVE", 20, 60)]
+        );
+    }
+
+    #[test]
+    fn not_enough_liquidity() {
+        let offers = vec![sell_offer("A", 50, 1, 1), sell_offer("B", 30, 1, 1)];
+        assert!(match_offers(&offers, SELL_OFFER, 81).is_none());
+        assert_eq!(
+            fills(&match_offers(&offers, SELL_OFFER, 80).unwrap()).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn buy_offers_matched_from_highest_price() {
+        let offers = vec![buy_offer("LOW", 100, 1, 1), buy_offer("HIGH", 40, 2, 1)];
+        let confirmations = match_offers(&offers, BUY_OFFER, 50).unwrap();
+        assert_eq!(
+            fills(&confirmations),
+            vec![("HIGH", 40, 80), ("LOW", 10, 10)]
+        );
+    }
+
+    #[test]
+    fn mixed_book_matched_by_offer_type() {
+        let offers = vec![
+            buy_offer("BUY_HIGH", 100, 5, 1),
+            sell_offer("SELL_HIGH", 100, 3, 1),
+            buy_offer("BUY_LOW", 100, 1, 1),
+            sell_offer("SELL_LOW", 100, 2, 1),
+        ];
+        let sells = match_offers(&offers, SELL_OFFER, 150).unwrap();
+        assert_eq!(
+            fills(&sells),
+            vec![("SELL_LOW", 100, 200), ("SELL_HIGH", 50, 150)]
+        );
+        let buys = match_offers(&offers, BUY_OFFER, 150).unwrap();
+        assert_eq!(
+            fills(&buys),
+            vec![("BUY_HIGH", 100, 500), ("BUY_LOW", 50, 50)]
+        );
+    }
+}
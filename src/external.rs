@@ -4,6 +4,10 @@ extern "C" {
 	pub fn __init() -> i64;
 	pub fn __inc() -> i64;
 	pub fn __write_log(msg: *const u8, len: usize) -> i64;
+	pub fn __set_exit_status(status: i64) -> i64;
+	pub fn __gas_remaining() -> i64;
+	pub fn __gas_used() -> i64;
+	pub fn get_gas_costs(data: *mut u8) -> i64;
 	pub fn save_sc_result(
 		file_ptr: *const u8,
 		file_len: usize,
@@ -11,7 +15,25 @@ extern "C" {
 		data_len: usize,
 	) -> i64;
 	pub fn get_from_storage(file_ptr: *const u8, file_len: usize, data: *mut u8) -> i64;
+	pub fn get_from_storage_range(
+		file_ptr: *const u8,
+		file_len: usize,
+		offset: u64,
+		length: usize,
+		data: *mut u8,
+	) -> i64;
 	pub fn get_http(url: *const u8, url_len: usize, body: *mut u8) -> i64;
+	pub fn drive_list_dir(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn drive_file_metadata(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn drive_remove(data_ptr: *const u8, data_len: usize) -> i64;
+	pub fn drive_create_dir(data_ptr: *const u8, data_len: usize) -> i64;
+	pub fn drive_rename(data_ptr: *const u8, data_len: usize) -> i64;
+	pub fn drive_append(
+		file_ptr: *const u8,
+		file_len: usize,
+		data_ptr: *const u8,
+		data_len: usize,
+	) -> i64;
 
 	//==================================================
 	// Blockchain Transactions manipulations
@@ -29,6 +51,8 @@ extern "C" {
 	pub fn secret_lock(data_ptr: *const u8, data_len: usize) -> i64;
 	pub fn secret_proof(data_ptr: *const u8, data_len: usize) -> i64;
 	pub fn transfer_with_namespace(data_ptr: *const u8, data_len: usize) -> i64;
+	pub fn transfer_mosaics(data_ptr: *const u8, data_len: usize) -> i64;
+	pub fn drive_fs(data_ptr: *const u8, data_len: usize) -> i64;
 	pub fn modify_metadata_address(data_ptr: *const u8, data_len: usize) -> i64;
 	pub fn modify_metadata_mosaic(data_ptr: *const u8, data_len: usize) -> i64;
 	pub fn modify_metadata_namespace(data_ptr: *const u8, data_len: usize) -> i64;
@@ -40,6 +64,7 @@ extern "C" {
 		data_len: usize,
 		data: *mut u8,
 	) -> i64;
+	pub fn get_exchange_offers(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
 	pub fn get_mosaic_info(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
 	pub fn get_mosaic_infos(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
 	pub fn get_mosaics_names(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
@@ -56,6 +81,26 @@ extern "C" {
 		data_len: usize,
 		data: *mut u8,
 	) -> i64;
+	pub fn get_namespace_info(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_namespaces_names(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_namespaces_from_account(
+		data_ptr: *const u8,
+		data_len: usize,
+		data: *mut u8,
+	) -> i64;
+	pub fn resolve_mosaic_alias(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn resolve_address_alias(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_address_metadata(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_mosaic_metadata(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_namespace_metadata(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn encrypt_message(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn decrypt_message(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_drive_info(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_drive_files(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_file_info(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
+	pub fn get_secret_lock_info(data_ptr: *const u8, data_len: usize, data: *mut u8) -> i64;
 	pub fn get_supercontract(data: *mut u8) -> i64;
+	pub fn get_contract_secret(data: *mut u8) -> i64;
 	pub fn get_initiator_pubkey(data: *mut u8) -> i64;
+	pub fn get_execution_context(data: *mut u8) -> i64;
 }
@@ -0,0 +1,110 @@
+//! # Gas introspection
+//!
+//! Every SuperContract execution has `Gas` limit. When limit
+//! reached, execution stopped by `WasmVM` without any chance
+//! to save progress. Functions of that module help to check
+//! `Gas` and stop gracefully.
+
+use std::collections::HashMap;
+
+use crate::external;
+use crate::statuses::{Error, FunctionResult, Result};
+use crate::tools::call::call_external_getter;
+
+/// Get remaining `Gas` of current execution.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::gas;
+/// let remaining = gas::remaining().unwrap();
+/// ```
+pub fn remaining() -> FunctionResult {
+    unsafe { Ok(external::__gas_remaining()) }
+}
+
+/// Get `Gas` used by current execution.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::gas;
+/// let used = gas::used().unwrap();
+/// ```
+pub fn used() -> FunctionResult {
+    unsafe { Ok(external::__gas_used()) }
+}
+
+/// Get `Gas` cost table of SDK functions from `WasmVM`.
+/// Key is `WasmVM` function name (for example `transfer`
+/// or `get_from_storage`), value is base `Gas` cost of the call.
+/// Data-dependent calls also spend `Gas` for data size.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::gas;
+/// let costs = gas::cost_table().unwrap();
+/// let transfer_cost = costs.get("transfer");
+/// ```
+pub fn cost_table() -> Result<HashMap<String, i64>> {
+    call_external_getter(external::get_gas_costs)
+}
+
+/// Gas budget guard for loops. Guard measure `Gas` spent
+/// by every step and fail with `Error::GasBudgetExceeded`
+/// when remaining `Gas` not enough for next step plus reserve.
+/// Reserve should cover work after loop, like saving progress.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::gas::Budget;
+/// use xpx_supercontracts_sdk::statuses::Error;
+/// use xpx_supercontracts_sdk::storage::save_result;
+///
+/// let items: Vec<u64> = vec![1, 2, 3];
+/// let mut budget = Budget::new(10_000);
+/// let mut processed = 0;
+/// for item in items.iter() {
+///     if let Err(Error::GasBudgetExceeded) = budget.check() {
+///         break;
+///     }
+///     // Process item
+///     processed += 1;
+/// }
+/// let cursor = processed.to_string();
+/// let _ = save_result(&"cursor.txt".to_string(), cursor.as_bytes());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Budget {
+    reserve: i64,
+    last_remaining: Option<i64>,
+    max_step: i64,
+}
+
+impl Budget {
+    /// Create guard that keep `reserve` of `Gas`
+    pub fn new(reserve: i64) -> Self {
+        Budget {
+            reserve,
+            last_remaining: None,
+            max_step: 0,
+        }
+    }
+
+    /// Max `Gas` spent by one step between checks
+    pub fn max_step(&self) -> i64 {
+        self.max_step
+    }
+
+    /// Check is remaining `Gas` enough for next step.
+    /// Should be called before every step.
+    pub fn check(&mut self) -> FunctionResult {
+        let remaining = remaining()?;
+        if let Some(last) = self.last_remaining {
+            self.max_step = self.max_step.max(last - remaining);
+        }
+        self.last_remaining = Some(remaining);
+        if remaining < self.reserve + self.max_step {
+            return Err(Error::GasBudgetExceeded);
+        }
+        Ok(remaining)
+    }
+}
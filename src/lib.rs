@@ -59,13 +59,29 @@
 //!
 extern crate serde;
 extern crate serde_json;
+extern crate chacha20poly1305;
+extern crate ripemd160;
+extern crate sha2;
+extern crate sha3;
 #[macro_use]
 extern crate failure;
 
+pub mod access;
+pub mod batch;
+pub mod cid;
+pub mod drive;
+pub mod escrow;
+pub mod exchange;
 mod external;
+pub mod gas;
 pub mod http;
+pub mod logging;
+pub mod merkle;
+pub mod metadata;
 pub mod statuses;
 pub mod storage;
+pub mod swap;
+pub mod token;
 mod tools;
 pub mod transactions;
 pub mod transactions_type;
@@ -0,0 +1,351 @@
+//! # Structured leveled logging
+//!
+//! Macros `sc_trace!`, `sc_debug!`, `sc_info!`, `sc_warn!` and
+//! `sc_error!` send log record to `WasmVM` as one JSON line:
+//! ```json
+//! {"Level":"INFO","Target":"my_contract","Message":"paid","Fields":{"amount":100}}
+//! ```
+//!
+//! Every log record spend `Gas`. Max level can be limited at
+//! compile time with crate features `max_level_*` and
+//! `release_max_level_*` (for release builds). Records above
+//! max level removed by compiler, so it has zero cost:
+//! ```ignore
+//! [dependencies]
+//! xpx_supercontracts_sdk = { version = "0.2", features = ["release_max_level_off"] }
+//! ```
+//!
+//! With crate feature `log` SDK provide backend
+//! for `log` crate macros, see `init_log_backend`.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::utils::debug_message;
+
+/// Log record level
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+/// Max level of log records
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LevelFilter {
+    Off = 0,
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl Level {
+    /// Check is level enabled by compile-time `STATIC_MAX_LEVEL`
+    pub const fn enabled(self) -> bool {
+        self as u8 <= STATIC_MAX_LEVEL as u8
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+/// Compile-time max level of log records defined by crate features
+pub const STATIC_MAX_LEVEL: LevelFilter = if cfg!(not(debug_assertions)) {
+    if cfg!(feature = "release_max_level_off") {
+        LevelFilter::Off
+    } else if cfg!(feature = "release_max_level_error") {
+        LevelFilter::Error
+    } else if cfg!(feature = "release_max_level_warn") {
+        LevelFilter::Warn
+    } else if cfg!(feature = "release_max_level_info") {
+        LevelFilter::Info
+    } else if cfg!(feature = "release_max_level_debug") {
+        LevelFilter::Debug
+    } else if cfg!(feature = "release_max_level_trace") {
+        LevelFilter::Trace
+    } else {
+        MAX_LEVEL
+    }
+} else {
+    MAX_LEVEL
+};
+
+const MAX_LEVEL: LevelFilter = if cfg!(feature = "max_level_off") {
+    LevelFilter::Off
+} else if cfg!(feature = "max_level_error") {
+    LevelFilter::Error
+} else if cfg!(feature = "max_level_warn") {
+    LevelFilter::Warn
+} else if cfg!(feature = "max_level_info") {
+    LevelFilter::Info
+} else if cfg!(feature = "max_level_debug") {
+    LevelFilter::Debug
+} else {
+    LevelFilter::Trace
+};
+
+/// Log record sent to `WasmVM`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Record<'a> {
+    level: &'static str,
+    target: &'a str,
+    message: &'a str,
+    fields: BTreeMap<&'a str, Value>,
+}
+
+/// Convert log field value to JSON. Value that can't be
+/// serialized logged as `null`.
+pub fn field<T: Serialize + ?Sized>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// Format log record as one JSON line. Fields sorted by key,
+/// for repeated key the last value kept.
+pub fn format_record(
+    level: Level,
+    target: &str,
+    message: &str,
+    fields: &[(&str, Value)],
+) -> String {
+    let record = Record {
+        level: level.as_str(),
+        target,
+        message,
+        fields: fields.iter().cloned().collect(),
+    };
+    serde_json::to_string(&record).unwrap_or_default()
+}
+
+/// Send log record to `WasmVM`. Use `sc_*!` macros instead,
+/// they check level at compile time.
+pub fn write_record(level: Level, target: &str, message: &str, fields: &[(&str, Value)]) {
+    debug_message(&format_record(level, target, message, fields));
+}
+
+/// Log record with specific level. Message is format string
+/// with arguments, key-value fields follow after `;`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::sc_log;
+/// use xpx_supercontracts_sdk::logging::Level;
+///
+/// sc_log!(Level::Info, "batch {} done", 3; processed = 100, failed = 0);
+/// ```
+#[macro_export]
+macro_rules! sc_log {
+    ($level:expr, $fmt:literal $(, $arg:expr)* $(; $($key:ident = $value:expr),+ )? $(,)?) => {
+        if $crate::logging::Level::enabled($level) {
+            $crate::logging::write_record(
+                $level,
+                module_path!(),
+                &format!($fmt $(, $arg)*),
+                &[$($((stringify!($key), $crate::logging::field(&$value))),+)?],
+            );
+        }
+    };
+}
+
+/// Log record with `Error` level.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::sc_error;
+/// sc_error!("transfer failed"; code = -1);
+/// ```
+#[macro_export]
+macro_rules! sc_error {
+    ($($arg:tt)+) => { $crate::sc_log!($crate::logging::Level::Error, $($arg)+) };
+}
+
+/// Log record with `Warn` level.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::sc_warn;
+/// sc_warn!("low balance"; balance = 10);
+/// ```
+#[macro_export]
+macro_rules! sc_warn {
+    ($($arg:tt)+) => { $crate::sc_log!($crate::logging::Level::Warn, $($arg)+) };
+}
+
+/// Log record with `Info` level.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::sc_info;
+/// let pub_key = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C";
+/// sc_info!("paid {} units", 100; to = pub_key);
+/// ```
+#[macro_export]
+macro_rules! sc_info {
+    ($($arg:tt)+) => { $crate::sc_log!($crate::logging::Level::Info, $($arg)+) };
+}
+
+/// Log record with `Debug` level.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::sc_debug;
+/// sc_debug!("state loaded"; version = 2);
+/// ```
+#[macro_export]
+macro_rules! sc_debug {
+    ($($arg:tt)+) => { $crate::sc_log!($crate::logging::Level::Debug, $($arg)+) };
+}
+
+/// Log record with `Trace` level.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::sc_trace;
+/// sc_trace!("enter function");
+/// ```
+#[macro_export]
+macro_rules! sc_trace {
+    ($($arg:tt)+) => { $crate::sc_log!($crate::logging::Level::Trace, $($arg)+) };
+}
+
+#[cfg(feature = "log")]
+mod backend {
+    use super::{write_record, Level, LevelFilter, STATIC_MAX_LEVEL};
+
+    /// Backend for `log` crate that send records to `WasmVM`
+    pub struct LogBackend;
+
+    impl log::Log for LogBackend {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::max_level()
+        }
+
+        fn log(&self, record: &log::Record) {
+            if !self.enabled(record.metadata()) {
+                return;
+            }
+            let level = match record.level() {
+                log::Level::Error => Level::Error,
+                log::Level::Warn => Level::Warn,
+                log::Level::Info => Level::Info,
+                log::Level::Debug => Level::Debug,
+                log::Level::Trace => Level::Trace,
+            };
+            write_record(level, record.target(), &record.args().to_string(), &[]);
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: LogBackend = LogBackend;
+
+    /// Install SDK as backend for `log` crate macros.
+    /// Max level is `STATIC_MAX_LEVEL`.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use xpx_supercontracts_sdk::logging::init_log_backend;
+    /// let _ = init_log_backend();
+    /// log::info!("SuperContract started");
+    /// ```
+    pub fn init_log_backend() -> Result<(), log::SetLoggerError> {
+        log::set_logger(&LOGGER)?;
+        log::set_max_level(match STATIC_MAX_LEVEL {
+            LevelFilter::Off => log::LevelFilter::Off,
+            LevelFilter::Error => log::LevelFilter::Error,
+            LevelFilter::Warn => log::LevelFilter::Warn,
+            LevelFilter::Info => log::LevelFilter::Info,
+            LevelFilter::Debug => log::LevelFilter::Debug,
+            LevelFilter::Trace => log::LevelFilter::Trace,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(feature = "log")]
+pub use self::backend::{init_log_backend, LogBackend};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVELS: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    #[test]
+    fn record_is_json_line() {
+        let line = format_record(Level::Info, "payouts", "paid \"all\"\n", &[]);
+        assert_eq!(
+            line,
+            r#"{"Level":"INFO","Target":"payouts","Message":"paid \"all\"\n","Fields":{}}"#
+        );
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn fields_sorted_by_key() {
+        let fields = [
+            ("recipient", field("2C81")),
+            ("amount", field(&100)),
+            ("memo", field(&None::<String>)),
+            ("amount", field(&200)),
+        ];
+        assert_eq!(
+            format_record(Level::Warn, "sc", "paid", &fields),
+            r#"{"Level":"WARN","Target":"sc","Message":"paid","Fields":{"amount":200,"memo":null,"recipient":"2C81"}}"#
+        );
+    }
+
+    #[test]
+    fn level_names() {
+        let names: Vec<&str> = LEVELS.iter().map(|level| level.as_str()).collect();
+        assert_eq!(names, vec!["ERROR", "WARN", "INFO", "DEBUG", "TRACE"]);
+    }
+
+    #[test]
+    fn levels_enabled_up_to_static_max_level() {
+        for level in LEVELS.iter() {
+            assert_eq!(level.enabled(), *level as u8 <= STATIC_MAX_LEVEL as u8);
+        }
+        assert!(LEVELS
+            .windows(2)
+            .all(|pair| pair[0].enabled() || !pair[1].enabled()));
+    }
+
+    #[test]
+    #[cfg(all(
+        debug_assertions,
+        not(any(
+            feature = "max_level_off",
+            feature = "max_level_error",
+            feature = "max_level_warn",
+            feature = "max_level_info",
+            feature = "max_level_debug"
+        ))
+    ))]
+    fn all_levels_enabled_by_default() {
+        assert_eq!(STATIC_MAX_LEVEL, LevelFilter::Trace);
+        assert!(LEVELS.iter().all(|level| level.enabled()));
+    }
+}
@@ -0,0 +1,354 @@
+//! # Merkle tree utilities
+//!
+//! Build Merkle trees, produce and verify inclusion proofs.
+//! Useful for airdrops and allowlists: only root hash kept
+//! on Drive or in SuperContract, and every caller provides
+//! proof for own entry.
+//!
+//! Leaf hash is hash of `0x00` byte and leaf data. Node hash
+//! is hash of `0x01` byte and concatenation of two child hashes.
+//! Different prefixes prevent passing inner node as leaf.
+//! Children ordering:
+//! * `PairOrdering::Sorted` - children sorted before hashing,
+//!   so proof doesn't need positions
+//! * `PairOrdering::Positional` - left child always first
+//!
+//! Node without pair promoted to the next level unchanged.
+//!
+//! Proof doesn't bind position of leaf in tree, so claims
+//! should be recorded by leaf hash (`HashAlgorithm::hash_leaf`),
+//! not by leaf index.
+//!
+//! Module doesn't depend on `WasmVM`, so the same code
+//! can generate proofs off-chain in native applications.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256, Sha3_256};
+
+use crate::tools::hex;
+
+/// Hash of tree node
+pub type MerkleHash = [u8; 32];
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash function of tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HashAlgorithm {
+    Sha3_256,
+    Keccak256,
+}
+
+impl HashAlgorithm {
+    /// Calculate hash of data
+    pub fn hash(self, data: &[u8]) -> MerkleHash {
+        let mut result = [0u8; 32];
+        match self {
+            HashAlgorithm::Sha3_256 => result.copy_from_slice(&Sha3_256::digest(data)),
+            HashAlgorithm::Keccak256 => result.copy_from_slice(&Keccak256::digest(data)),
+        }
+        result
+    }
+
+    /// Calculate leaf hash of leaf data
+    pub fn hash_leaf(self, leaf: &[u8]) -> MerkleHash {
+        let mut data = Vec::with_capacity(leaf.len() + 1);
+        data.push(LEAF_PREFIX);
+        data.extend_from_slice(leaf);
+        self.hash(&data)
+    }
+
+    fn hash_pair(self, left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+        let mut data = [0u8; 65];
+        data[0] = NODE_PREFIX;
+        data[1..33].copy_from_slice(left);
+        data[33..].copy_from_slice(right);
+        self.hash(&data)
+    }
+}
+
+/// How pair of nodes ordered before hashing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PairOrdering {
+    Sorted,
+    Positional,
+}
+
+/// Sibling node in inclusion proof
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProofNode {
+    /// Sibling hash as hex string
+    pub hash: String,
+    /// Is sibling on the left side. Ignored for `PairOrdering::Sorted`
+    pub is_left: bool,
+}
+
+/// Inclusion proof of leaf. Proof prove only that leaf
+/// data included to tree, not leaf position.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MerkleProof {
+    pub siblings: Vec<ProofNode>,
+}
+
+impl MerkleProof {
+    /// Calculate root for leaf data according to proof.
+    /// Return `None` if proof contains invalid hash.
+    pub fn root(
+        &self,
+        leaf: &[u8],
+        algorithm: HashAlgorithm,
+        ordering: PairOrdering,
+    ) -> Option<MerkleHash> {
+        let mut node = algorithm.hash_leaf(leaf);
+        for sibling in self.siblings.iter() {
+            let sibling_hash = decode_hash(&sibling.hash)?;
+            let sibling_is_left = match ordering {
+                PairOrdering::Sorted => sibling_hash < node,
+                PairOrdering::Positional => sibling.is_left,
+            };
+            node = if sibling_is_left {
+                algorithm.hash_pair(&sibling_hash, &node)
+            } else {
+                algorithm.hash_pair(&node, &sibling_hash)
+            };
+        }
+        Some(node)
+    }
+
+    /// Verify is leaf data included to tree with the given root.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use xpx_supercontracts_sdk::merkle::{HashAlgorithm, MerkleProof, PairOrdering};
+    ///
+    /// let proof: MerkleProof = serde_json::from_str(r#"{"Siblings":[]}"#).unwrap();
+    /// let root_hex = "5A5D8F33FBA9B1B7CB8E3A9A12B5C0E1FA3E1D7F2F2D35E9E3C2F00F61D6C3E4";
+    /// let valid = proof.verify(b"some leaf", root_hex, HashAlgorithm::Sha3_256, PairOrdering::Sorted);
+    /// ```
+    pub fn verify(
+        &self,
+        leaf: &[u8],
+        root: &str,
+        algorithm: HashAlgorithm,
+        ordering: PairOrdering,
+    ) -> bool {
+        match (self.root(leaf, algorithm, ordering), decode_hash(root)) {
+            (Some(calculated), Some(expected)) => calculated == expected,
+            _ => false,
+        }
+    }
+}
+
+/// Merkle tree with all levels
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    algorithm: HashAlgorithm,
+    ordering: PairOrdering,
+    levels: Vec<Vec<MerkleHash>>,
+}
+
+impl MerkleTree {
+    /// Build tree from leaves data.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use xpx_supercontracts_sdk::merkle::{HashAlgorithm, MerkleTree, PairOrdering};
+    ///
+    /// let leaves = vec!["alice:100", "bob:200", "carol:300"];
+    /// let tree = MerkleTree::new(&leaves, HashAlgorithm::Keccak256, PairOrdering::Sorted);
+    /// let root = tree.root_hex();
+    /// let proof = tree.proof(1).unwrap();
+    /// assert!(proof.verify(b"bob:200", &root, HashAlgorithm::Keccak256, PairOrdering::Sorted));
+    /// ```
+    pub fn new<T: AsRef<[u8]>>(
+        leaves: &[T],
+        algorithm: HashAlgorithm,
+        ordering: PairOrdering,
+    ) -> Self {
+        let mut levels = vec![leaves
+            .iter()
+            .map(|leaf| algorithm.hash_leaf(leaf.as_ref()))
+            .collect::<Vec<MerkleHash>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let level = &levels[levels.len() - 1];
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => match ordering {
+                        PairOrdering::Sorted if right < left => algorithm.hash_pair(right, left),
+                        _ => algorithm.hash_pair(left, right),
+                    },
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        MerkleTree {
+            algorithm,
+            ordering,
+            levels,
+        }
+    }
+
+    /// Tree root. Root of empty tree is hash of empty data.
+    pub fn root(&self) -> MerkleHash {
+        match self.levels[self.levels.len() - 1].first() {
+            Some(root) => *root,
+            None => self.algorithm.hash(&[]),
+        }
+    }
+
+    /// Tree root as hex string
+    pub fn root_hex(&self) -> String {
+        hex::encode(&self.root())
+    }
+
+    /// Number of leaves
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Is tree without leaves
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Build inclusion proof for leaf. Return `None`
+    /// if leaf index out of range.
+    pub fn proof(&self, leaf_index: usize) -> Option<MerkleProof> {
+        if leaf_index >= self.len() {
+            return None;
+        }
+        let mut index = leaf_index;
+        let mut siblings = vec![];
+        for level in self.levels[..self.levels.len() - 1].iter() {
+            let sibling_index = index ^ 1;
+            if let Some(sibling) = level.get(sibling_index) {
+                siblings.push(ProofNode {
+                    hash: hex::encode(sibling),
+                    is_left: sibling_index < index,
+                });
+            }
+            index /= 2;
+        }
+        Some(MerkleProof { siblings })
+    }
+
+    /// Build inclusion proofs for all leaves. Useful to
+    /// generate proofs off-chain and distribute them to callers.
+    pub fn proofs(&self) -> Vec<MerkleProof> {
+        (0..self.len()).filter_map(|index| self.proof(index)).collect()
+    }
+
+    /// Verify leaf data with tree settings
+    pub fn verify(&self, proof: &MerkleProof, leaf: &[u8]) -> bool {
+        proof.verify(leaf, &self.root_hex(), self.algorithm, self.ordering)
+    }
+}
+
+fn decode_hash(data: &str) -> Option<MerkleHash> {
+    let bytes = hex::decode(data)?;
+    if bytes.len() != 32 {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bytes);
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALGORITHMS: [HashAlgorithm; 2] = [HashAlgorithm::Sha3_256, HashAlgorithm::Keccak256];
+    const ORDERINGS: [PairOrdering; 2] = [PairOrdering::Sorted, PairOrdering::Positional];
+
+    fn leaves(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("account-{}:{}", i, i * 100)).collect()
+    }
+
+    #[test]
+    fn proofs_of_all_leaves_verify() {
+        for algorithm in ALGORITHMS.iter() {
+            for ordering in ORDERINGS.iter() {
+                for count in 1..=9 {
+                    let leaves = leaves(count);
+                    let tree = MerkleTree::new(&leaves, *algorithm, *ordering);
+                    let root = tree.root_hex();
+                    for (index, leaf) in leaves.iter().enumerate() {
+                        let proof = tree.proof(index).unwrap();
+                        assert!(proof.verify(leaf.as_bytes(), &root, *algorithm, *ordering));
+                        assert!(tree.verify(&proof, leaf.as_bytes()));
+                    }
+                    assert!(tree.proof(count).is_none());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn proof_of_other_leaf_rejected() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(&leaves, HashAlgorithm::Keccak256, PairOrdering::Sorted);
+        let proof = tree.proof(1).unwrap();
+        assert!(!tree.verify(&proof, leaves[2].as_bytes()));
+        assert!(!tree.verify(&proof, b"unknown"));
+    }
+
+    #[test]
+    fn single_leaf_root_is_leaf_hash() {
+        let tree = MerkleTree::new(&["only"], HashAlgorithm::Sha3_256, PairOrdering::Sorted);
+        assert_eq!(tree.root(), HashAlgorithm::Sha3_256.hash_leaf(b"only"));
+        assert_eq!(tree.len(), 1);
+        assert!(tree.proof(0).unwrap().siblings.is_empty());
+    }
+
+    #[test]
+    fn empty_tree() {
+        let leaves: Vec<&str> = vec![];
+        let tree = MerkleTree::new(&leaves, HashAlgorithm::Sha3_256, PairOrdering::Sorted);
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), HashAlgorithm::Sha3_256.hash(&[]));
+        assert!(tree.proofs().is_empty());
+    }
+
+    #[test]
+    fn inner_node_as_leaf_rejected() {
+        for ordering in ORDERINGS.iter() {
+            let algorithm = HashAlgorithm::Sha3_256;
+            let leaves = leaves(4);
+            let tree = MerkleTree::new(&leaves, algorithm, *ordering);
+            let proof = tree.proof(0).unwrap();
+
+            // Forge 64 bytes "leaf" from hashes of first two leaves
+            let mut left = algorithm.hash_leaf(leaves[0].as_bytes());
+            let mut right = algorithm.hash_leaf(leaves[1].as_bytes());
+            if *ordering == PairOrdering::Sorted && right < left {
+                std::mem::swap(&mut left, &mut right);
+            }
+            let mut forged_leaf = left.to_vec();
+            forged_leaf.extend_from_slice(&right);
+            let forged_proof = MerkleProof {
+                siblings: vec![proof.siblings[1].clone()],
+            };
+            assert!(!tree.verify(&forged_proof, &forged_leaf));
+        }
+    }
+
+    #[test]
+    fn invalid_proof_hash_rejected() {
+        let tree = MerkleTree::new(&leaves(2), HashAlgorithm::Sha3_256, PairOrdering::Sorted);
+        let proof = MerkleProof {
+            siblings: vec![ProofNode {
+                hash: "ZZ".to_string(),
+                is_left: false,
+            }],
+        };
+        assert!(proof.root(b"account-0:0", HashAlgorithm::Sha3_256, PairOrdering::Sorted).is_none());
+        assert!(!tree.verify(&proof, b"account-0:0"));
+    }
+}
@@ -0,0 +1,117 @@
+//! # Metadata helpers
+//!
+//! Tools to keep account, mosaic and namespace metadata
+//! in desired state with minimal set of modifications.
+
+use std::collections::HashMap;
+
+use crate::transactions_type::{
+    MetadataModification, METADATA_MODIFICATION_ADD, METADATA_MODIFICATION_REMOVE,
+};
+
+/// Calculate minimal list of `MetadataModification` that
+/// transform `current` metadata fields to `desired` fields.
+///
+/// * key exist only in `current` - removed
+/// * key exist only in `desired` - added
+/// * key value changed - old value removed and new value added
+///
+/// Result sorted by key, so all Executors produce
+/// the same transaction.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use std::collections::HashMap;
+/// use xpx_supercontracts_sdk::metadata::diff_metadata;
+/// use xpx_supercontracts_sdk::transactions::{get_mosaic_metadata, modify_metadata_mosaic};
+/// use xpx_supercontracts_sdk::transactions_type::{GetMosaicMetadata, ModifyMetadataMosaic};
+///
+/// let mut desired: HashMap<String, String> = HashMap::new();
+/// desired.insert("fee".to_string(), "10".to_string());
+///
+/// let current = get_mosaic_metadata(&GetMosaicMetadata { mosaic_id: 10 }).unwrap();
+/// let current = current.map(|info| info.fields).unwrap_or_default();
+/// let modifications = diff_metadata(&current, &desired);
+/// if !modifications.is_empty() {
+///     let _ = modify_metadata_mosaic(&ModifyMetadataMosaic {
+///         mosaic_id: Some(10),
+///         modifications: Some(modifications),
+///     });
+/// }
+/// ```
+pub fn diff_metadata(
+    current: &HashMap<String, String>,
+    desired: &HashMap<String, String>,
+) -> Vec<MetadataModification> {
+    let mut keys: Vec<&String> = current.keys().chain(desired.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut modifications = vec![];
+    for key in keys {
+        match (current.get(key), desired.get(key)) {
+            (Some(old), Some(new)) if old == new => {}
+            (Some(old), new) => {
+                modifications.push(MetadataModification {
+                    modification_type: METADATA_MODIFICATION_REMOVE,
+                    key: key.clone(),
+                    value: old.clone(),
+                });
+                if let Some(new) = new {
+                    modifications.push(MetadataModification {
+                        modification_type: METADATA_MODIFICATION_ADD,
+                        key: key.clone(),
+                        value: new.clone(),
+                    });
+                }
+            }
+            (None, Some(new)) => modifications.push(MetadataModification {
+                modification_type: METADATA_MODIFICATION_ADD,
+                key: key.clone(),
+                value: new.clone(),
+            }),
+            (None, None) => {}
+        }
+    }
+    modifications
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn summary(modifications: &[MetadataModification]) -> Vec<(u8, &str, &str)> {
+        modifications
+            .iter()
+            .map(|m| (m.modification_type, m.key.as_str(), m.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn equal_metadata_has_no_modifications() {
+        let current = fields(&[("fee", "10"), ("name", "token")]);
+        assert!(diff_metadata(&current, &current.clone()).is_empty());
+    }
+
+    #[test]
+    fn modifications_sorted_by_key() {
+        let current = fields(&[("b", "old"), ("c", "removed"), ("d", "same")]);
+        let desired = fields(&[("a", "added"), ("b", "new"), ("d", "same")]);
+        assert_eq!(
+            summary(&diff_metadata(&current, &desired)),
+            vec![
+                (METADATA_MODIFICATION_ADD, "a", "added"),
+                (METADATA_MODIFICATION_REMOVE, "b", "old"),
+                (METADATA_MODIFICATION_ADD, "b", "new"),
+                (METADATA_MODIFICATION_REMOVE, "c", "removed"),
+            ]
+        );
+    }
+}
@@ -9,6 +9,22 @@ pub type MultipleFunctionResult = Result<Vec<u8>>;
 /// when function don't has own result.
 pub const STATUS_SUCCESS: i64 = 0;
 
+/// Convert negative status of `WasmVM` function to
+/// `Error::FunctionFailed`. Non-negative status returned as is.
+///
+/// # Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::statuses::{check_status, Error};
+/// assert_eq!(check_status(0).unwrap(), 0);
+/// assert!(matches!(check_status(-1), Err(Error::FunctionFailed)));
+/// ```
+pub fn check_status(status: i64) -> FunctionResult {
+    if status < 0 {
+        return Err(Error::FunctionFailed);
+    }
+    Ok(status)
+}
+
 /// Result of unsuccessful supercontract execution.
 ///
 /// An execution error consists
@@ -55,4 +71,54 @@ pub enum Error {
     SerializeJson = -1,
     #[fail(display = "Failed deserialize the given byte data to structure")]
     DeserializeJson = -2,
+    #[fail(display = "Failed decode the given hex string")]
+    DecodeHex = -3,
+    #[fail(display = "Invalid drive path: absolute paths and `..` are not allowed")]
+    InvalidPath = -4,
+    #[fail(display = "Failed read or write CSV data")]
+    Csv = -5,
+    #[fail(display = "Invalid or unsupported content ID")]
+    InvalidCid = -6,
+    #[fail(display = "Failed encrypt data for Storage")]
+    EncryptStorage = -7,
+    #[fail(display = "Failed decrypt Storage file: data corrupted or wrong key")]
+    DecryptStorage = -8,
+    #[fail(display = "Unsupported state schema version")]
+    UnsupportedSchemaVersion = -9,
+    #[fail(display = "Gas budget exceeded, execution stopped before Gas limit")]
+    GasBudgetExceeded = -10,
+    #[fail(display = "Initiator is not owner of SuperContract")]
+    NotOwner = -11,
+    #[fail(display = "Initiator doesn't have required role")]
+    MissingRole = -12,
+    #[fail(display = "Owner of SuperContract already set")]
+    OwnerAlreadySet = -13,
+    #[fail(display = "Initiator is not pending owner of SuperContract")]
+    NotPendingOwner = -14,
+    #[fail(display = "Invalid token amount")]
+    InvalidAmount = -15,
+    #[fail(display = "Unique asset not found in collection")]
+    UnknownAsset = -16,
+    #[fail(display = "Unique asset not held by SuperContract")]
+    AssetNotHeld = -17,
+    #[fail(display = "Operation not allowed in current swap state")]
+    InvalidSwapState = -18,
+    #[fail(display = "Unsupported secret lock hash type")]
+    UnsupportedHashType = -19,
+    #[fail(display = "Operation not allowed in current escrow state")]
+    InvalidEscrowState = -20,
+    #[fail(display = "Initiator is not allowed to perform escrow operation")]
+    NotEscrowParty = -21,
+    #[fail(display = "WasmVM function returned error status")]
+    FunctionFailed = -22,
+    #[fail(display = "Unique asset already issued in collection")]
+    AssetAlreadyExists = -23,
+    #[fail(display = "Blockchain height unknown in execution context")]
+    UnknownHeight = -24,
+    #[fail(display = "Execute transaction mosaics don't cover escrow deposit")]
+    InsufficientDeposit = -25,
+    #[fail(display = "Drive root hashes have different length")]
+    RootHashLength = -26,
+    #[fail(display = "Drive not found")]
+    UnknownDrive = -27,
 }
@@ -0,0 +1,287 @@
+//! Typed CSV datasets in Storage.
+//!
+//! Rows streamed through chunked `open` and `create`,
+//! so whole file never loaded to memory.
+
+use std::fmt;
+use std::io::{Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::stream::{create, open};
+use crate::statuses::{Error, ExecutionError};
+
+/// Default limit of rows for `read`
+pub const DEFAULT_MAX_ROWS: usize = 10_000;
+
+/// CSV reading options
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Columns that should be present in file header
+    pub required_headers: Vec<String>,
+    /// Max number of data rows. Reading fail if file has more rows
+    pub max_rows: usize,
+    pub delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            required_headers: vec![],
+            max_rows: DEFAULT_MAX_ROWS,
+            delimiter: b',',
+        }
+    }
+}
+
+/// Error of CSV dataset processing
+#[derive(Debug, Fail)]
+pub enum CsvError {
+    #[fail(display = "Storage error: {}", _0)]
+    Storage(Error),
+    #[fail(display = "Missing CSV headers: {:?}", _0)]
+    MissingHeaders(Vec<String>),
+    #[fail(display = "CSV line {}: {}", line, message)]
+    Row { line: u64, message: String },
+    #[fail(display = "CSV file has more than {} rows", _0)]
+    TooManyRows(usize),
+    #[fail(display = "CSV write error: {}", _0)]
+    Write(String),
+}
+
+impl From<Error> for CsvError {
+    fn from(value: Error) -> CsvError {
+        CsvError::Storage(value)
+    }
+}
+
+impl From<CsvError> for Error {
+    fn from(value: CsvError) -> Error {
+        match value {
+            CsvError::Storage(err) => err,
+            _ => Error::Csv,
+        }
+    }
+}
+
+impl From<CsvError> for ExecutionError {
+    fn from(value: CsvError) -> ExecutionError {
+        let description = format!("{}", value);
+        let code = Error::from(value) as i8;
+        ExecutionError::with_description(code, description)
+    }
+}
+
+fn row_error<T: fmt::Display>(line: u64, err: T) -> CsvError {
+    CsvError::Row {
+        line,
+        message: format!("{}", err),
+    }
+}
+
+fn error_line(err: &::csv::Error) -> u64 {
+    err.position().map(|pos| pos.line()).unwrap_or(0)
+}
+
+/// Read all rows of CSV file with default options.
+/// First line of file should be header.
+///
+/// # Examples
+/// ```rust,no_run
+/// use serde::Deserialize;
+/// use xpx_supercontracts_sdk::storage::csv;
+///
+/// #[derive(Deserialize)]
+/// struct Payout {
+///     pub_key: String,
+///     amount: i64,
+/// }
+///
+/// let rows: Vec<Payout> = csv::read("payroll.csv").unwrap();
+/// ```
+pub fn read<T: DeserializeOwned>(file_name: &str) -> Result<Vec<T>, CsvError> {
+    read_with(file_name, &CsvOptions::default())
+}
+
+/// Read all rows of CSV file with specific options.
+///
+/// # Examples
+/// ```rust,no_run
+/// use serde::Deserialize;
+/// use xpx_supercontracts_sdk::storage::csv::{self, CsvError, CsvOptions};
+///
+/// #[derive(Deserialize)]
+/// struct Airdrop {
+///     address: String,
+///     amount: i64,
+/// }
+///
+/// let options = CsvOptions {
+///     required_headers: vec!["address".to_string(), "amount".to_string()],
+///     max_rows: 500,
+///     ..CsvOptions::default()
+/// };
+/// match csv::read_with::<Airdrop>("airdrop.csv", &options) {
+///     Ok(rows) => {}
+///     Err(CsvError::Row { line, message }) => {
+///         // Report broken line
+///     }
+///     Err(err) => {}
+/// }
+/// ```
+pub fn read_with<T: DeserializeOwned>(
+    file_name: &str,
+    options: &CsvOptions,
+) -> Result<Vec<T>, CsvError> {
+    read_from(open(file_name)?, options)
+}
+
+/// Read all rows of CSV data from any reader with specific options.
+/// `read_with` use it for Storage files.
+pub fn read_from<T: DeserializeOwned, R: Read>(
+    reader: R,
+    options: &CsvOptions,
+) -> Result<Vec<T>, CsvError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .from_reader(reader);
+
+    let headers = reader
+        .headers()
+        .map_err(|err| row_error(error_line(&err).max(1), err))?;
+    let missing: Vec<String> = options
+        .required_headers
+        .iter()
+        .filter(|required| !headers.iter().any(|header| header == required.as_str()))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(CsvError::MissingHeaders(missing));
+    }
+
+    let mut rows = vec![];
+    for row in reader.deserialize() {
+        if rows.len() >= options.max_rows {
+            return Err(CsvError::TooManyRows(options.max_rows));
+        }
+        let row: T = row.map_err(|err| row_error(error_line(&err), err))?;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Write rows to Supercontract result CSV file.
+/// Header generated from fields of `T`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use serde::Serialize;
+/// use xpx_supercontracts_sdk::storage::csv;
+///
+/// #[derive(Serialize)]
+/// struct Receipt {
+///     pub_key: String,
+///     paid: i64,
+/// }
+///
+/// let rows = vec![Receipt { pub_key: "2C81".to_string(), paid: 100 }];
+/// csv::write("receipts.csv", &rows).unwrap();
+/// ```
+pub fn write<T: Serialize>(file_name: &str, rows: &[T]) -> Result<(), CsvError> {
+    write_to(create(file_name)?, rows)
+}
+
+/// Write rows as CSV data to any writer.
+/// `write` use it for Storage files.
+pub fn write_to<T: Serialize, W: Write>(writer: W, rows: &[T]) -> Result<(), CsvError> {
+    let mut writer = ::csv::Writer::from_writer(writer);
+    for row in rows {
+        writer
+            .serialize(row)
+            .map_err(|err| CsvError::Write(format!("{}", err)))?;
+    }
+    writer
+        .flush()
+        .map_err(|err| CsvError::Write(format!("{}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Payout {
+        pub_key: String,
+        amount: i64,
+    }
+
+    fn payout(pub_key: &str, amount: i64) -> Payout {
+        Payout {
+            pub_key: pub_key.to_string(),
+            amount,
+        }
+    }
+
+    fn read(data: &str, options: &CsvOptions) -> Result<Vec<Payout>, CsvError> {
+        read_from(data.as_bytes(), options)
+    }
+
+    #[test]
+    fn write_read_round_trip() {
+        let rows = vec![payout("2C81", 100), payout("F8A5", 25)];
+        let mut data = vec![];
+        write_to(&mut data, &rows).unwrap();
+        let data = String::from_utf8(data).unwrap();
+        assert_eq!(data, "pub_key,amount\n2C81,100\nF8A5,25\n");
+        assert_eq!(read(&data, &CsvOptions::default()).unwrap(), rows);
+    }
+
+    #[test]
+    fn missing_headers_reported() {
+        let options = CsvOptions {
+            required_headers: vec!["pub_key".to_string(), "amount".to_string()],
+            ..CsvOptions::default()
+        };
+        match read("pub_key,value\n2C81,100\n", &options) {
+            Err(CsvError::MissingHeaders(missing)) => assert_eq!(missing, vec!["amount"]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(read("pub_key,amount\n", &options).unwrap().is_empty());
+    }
+
+    #[test]
+    fn row_error_has_line_number() {
+        let data = "pub_key,amount\n2C81,100\nF8A5,ten\n";
+        match read(data, &CsvOptions::default()) {
+            Err(CsvError::Row { line, .. }) => assert_eq!(line, 3),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn too_many_rows_rejected() {
+        let options = CsvOptions {
+            max_rows: 2,
+            ..CsvOptions::default()
+        };
+        let data = "pub_key,amount\n2C81,1\nF8A5,2\n";
+        assert_eq!(read(data, &options).unwrap().len(), 2);
+        match read(&format!("{}AB12,3\n", data), &options) {
+            Err(CsvError::TooManyRows(2)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn custom_delimiter() {
+        let options = CsvOptions {
+            delimiter: b';',
+            ..CsvOptions::default()
+        };
+        assert_eq!(
+            read("pub_key;amount\n2C81;100\n", &options).unwrap(),
+            vec![payout("2C81", 100)]
+        );
+    }
+}
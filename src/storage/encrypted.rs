@@ -0,0 +1,229 @@
+//! Encrypted Storage files.
+//!
+//! Data encrypted with ChaCha20-Poly1305 before `save_result`
+//! and decrypted after `storage_get`, so replicators of Drive
+//! see only ciphertext. File name used as associated data,
+//! so encrypted file can't be moved to another path.
+//!
+//! All Executors should produce the same result file, so nonce
+//! is derived from key, file name and data instead of random.
+//! As consequence, the same data saved to the same file
+//! produce the same ciphertext.
+//!
+//! File format: `XSE1` magic, 12 bytes nonce, ciphertext with 16 bytes tag,
+//! so encrypted file is never empty.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use sha2::{Digest, Sha256};
+
+use crate::external;
+use crate::statuses::{Error, FunctionResult, MultipleFunctionResult, Result};
+
+const MAGIC: &[u8; 4] = b"XSE1";
+const NONCE_LEN: usize = 12;
+const SECRET_LEN: usize = 32;
+const KEY_CONTEXT: &[u8] = b"xpx-supercontracts-storage-key";
+const NONCE_CONTEXT: &[u8] = b"xpx-supercontracts-storage-nonce";
+
+/// Key for encrypted Storage files
+#[derive(Clone)]
+pub struct StorageKey([u8; 32]);
+
+impl StorageKey {
+    /// Derive key from secret of SuperContract provided by `WasmVM`.
+    /// Secret is the same for all Executors of SuperContract.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use xpx_supercontracts_sdk::storage::encrypted::StorageKey;
+    /// let key = StorageKey::from_contract_secret().unwrap();
+    /// ```
+    pub fn from_contract_secret() -> Result<StorageKey> {
+        let mut secret = vec![0u8; SECRET_LEN * 2];
+        let secret_len = unsafe { external::get_contract_secret(secret.as_mut_ptr()) };
+        if secret_len != SECRET_LEN as i64 {
+            return Err(Error::EncryptStorage);
+        }
+        Ok(StorageKey::derive(&secret[..SECRET_LEN]))
+    }
+
+    /// Derive key from caller-provided key material.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use xpx_supercontracts_sdk::storage::encrypted::StorageKey;
+    /// let key = StorageKey::from_bytes(b"caller provided key material");
+    /// ```
+    pub fn from_bytes(key_material: &[u8]) -> StorageKey {
+        StorageKey::derive(key_material)
+    }
+
+    fn derive(key_material: &[u8]) -> StorageKey {
+        let mut hasher = Sha256::new();
+        hasher.input(KEY_CONTEXT);
+        hasher.input(key_material);
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&hasher.result());
+        StorageKey(key)
+    }
+
+    fn nonce(&self, file_name: &str, data: &[u8]) -> [u8; NONCE_LEN] {
+        let mut hasher = Sha256::new();
+        hasher.input(NONCE_CONTEXT);
+        hasher.input(self.0);
+        hasher.input((file_name.len() as u64).to_le_bytes());
+        hasher.input(file_name.as_bytes());
+        hasher.input(data);
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&hasher.result()[..NONCE_LEN]);
+        nonce
+    }
+}
+
+impl std::fmt::Debug for StorageKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "StorageKey(..)")
+    }
+}
+
+/// Encrypt data for specific file.
+pub fn encrypt(key: &StorageKey, file_name: &str, data: &[u8]) -> Result<Vec<u8>> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key.0));
+    let nonce = key.nonce(file_name, data);
+    let payload = Payload {
+        msg: data,
+        aad: file_name.as_bytes(),
+    };
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), payload)
+        .map_err(|_| Error::EncryptStorage)?;
+
+    let mut result = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    result.extend_from_slice(MAGIC);
+    result.extend_from_slice(&nonce);
+    result.extend(ciphertext);
+    Ok(result)
+}
+
+/// Decrypt data of specific file. Return `Error::DecryptStorage`
+/// if data was changed, encrypted for another file or with another key.
+pub fn decrypt(key: &StorageKey, file_name: &str, data: &[u8]) -> Result<Vec<u8>> {
+    if data.len() < MAGIC.len() + NONCE_LEN || &data[..MAGIC.len()] != MAGIC {
+        return Err(Error::DecryptStorage);
+    }
+    let (nonce, ciphertext) = data[MAGIC.len()..].split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key.0));
+    let payload = Payload {
+        msg: ciphertext,
+        aad: file_name.as_bytes(),
+    };
+    cipher
+        .decrypt(Nonce::from_slice(nonce), payload)
+        .map_err(|_| Error::DecryptStorage)
+}
+
+/// Read and decrypt file from Storage. Encrypted
+/// version of `storage::storage_get`.
+/// If file not exist or empty function return empty array.
+///
+/// Encrypted file is never empty, even for empty data, but Storage
+/// doesn't distinguish missing and empty files. So file truncated
+/// to zero length by replicator looks like missing file. Use
+/// `storage_get_existing` for files that should always exist.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::statuses::Error;
+/// use xpx_supercontracts_sdk::storage::encrypted::{storage_get, StorageKey};
+///
+/// let key = StorageKey::from_contract_secret().unwrap();
+/// match storage_get(&"records.json".to_string(), &key) {
+///     Ok(data) => {}
+///     Err(Error::DecryptStorage) => {
+///         // File was corrupted
+///     }
+///     Err(err) => {}
+/// }
+/// ```
+pub fn storage_get(file_name: &String, key: &StorageKey) -> MultipleFunctionResult {
+    let data = super::storage_get(file_name)?;
+    if data.is_empty() {
+        return Ok(data);
+    }
+    decrypt(key, file_name, &data)
+}
+
+/// Read and decrypt file that should exist in Storage.
+/// Missing or empty file reported as `Error::DecryptStorage`,
+/// so truncated file can't be taken for missing one.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::storage::encrypted::{storage_get_existing, StorageKey};
+///
+/// let key = StorageKey::from_contract_secret().unwrap();
+/// let config = storage_get_existing(&"config.json".to_string(), &key);
+/// ```
+pub fn storage_get_existing(file_name: &String, key: &StorageKey) -> MultipleFunctionResult {
+    let data = super::storage_get(file_name)?;
+    decrypt(key, file_name, &data)
+}
+
+/// Encrypt and save results of Supercotract execution to Storage.
+/// Encrypted version of `storage::save_result`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::storage::encrypted::{save_result, StorageKey};
+///
+/// let key = StorageKey::from_contract_secret().unwrap();
+/// let result_status = save_result(&"records.json".to_string(), b"{}", &key);
+/// assert_eq!(result_status.unwrap(), 0);
+/// ```
+pub fn save_result(file_name: &String, data: &[u8], key: &StorageKey) -> FunctionResult {
+    let data = encrypt(key, file_name, data)?;
+    super::save_result(file_name, &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "secret/config.json";
+
+    #[test]
+    fn round_trip() {
+        let key = StorageKey::from_bytes(b"key");
+        for data in [&b""[..], b"{\"Limit\":100}"].iter() {
+            let encrypted = encrypt(&key, FILE, data).unwrap();
+            assert_eq!(&encrypted[..MAGIC.len()], MAGIC);
+            assert_eq!(encrypted.len(), MAGIC.len() + NONCE_LEN + data.len() + 16);
+            assert_eq!(decrypt(&key, FILE, &encrypted).unwrap(), *data);
+        }
+    }
+
+    #[test]
+    fn encryption_deterministic() {
+        let key = StorageKey::from_bytes(b"key");
+        let encrypted = encrypt(&key, FILE, b"data").unwrap();
+        assert_eq!(encrypt(&key, FILE, b"data").unwrap(), encrypted);
+        assert_ne!(encrypt(&key, FILE, b"other").unwrap(), encrypted);
+    }
+
+    #[test]
+    fn wrong_key_file_or_data_rejected() {
+        let key = StorageKey::from_bytes(b"key");
+        let encrypted = encrypt(&key, FILE, b"data").unwrap();
+        let other_key = StorageKey::from_bytes(b"other key");
+        assert!(decrypt(&other_key, FILE, &encrypted).is_err());
+        assert!(decrypt(&key, "secret/other.json", &encrypted).is_err());
+
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&key, FILE, &tampered).is_err());
+        assert!(decrypt(&key, FILE, &encrypted[..MAGIC.len() + NONCE_LEN - 1]).is_err());
+        assert!(decrypt(&key, FILE, &[]).is_err());
+        assert!(decrypt(&key, FILE, b"{\"Limit\":100}").is_err());
+    }
+}
@@ -2,6 +2,18 @@
 //!
 //! File always located inside `/root/supercontracts/` directory.
 //! So it should be relative path like: `path/to/my/file.json`
+//!
+//! For large files use chunked `open` and `create`.
+//! Typed CSV datasets available in `storage::csv`,
+//! encrypted files in `storage::encrypted` and
+//! versioned state with migrations in `storage::state`.
+
+pub mod csv;
+pub mod encrypted;
+pub mod state;
+mod stream;
+
+pub use self::stream::{create, open, StorageReader, StorageWriter, DEFAULT_CHUNK_SIZE};
 
 use crate::external;
 use crate::statuses::{FunctionResult, MultipleFunctionResult};
@@ -0,0 +1,214 @@
+//! Versioned SuperContract state.
+//!
+//! State saved to Storage inside envelope with schema version.
+//! When SuperContract upgraded, registered migrations
+//! transform old state to current schema on load.
+//!
+//! State saved without envelope (by SuperContract before
+//! versioning) treated as schema version `0`, or as version
+//! declared with `Migrations::legacy_version`.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::statuses::{Error, FunctionResult, Result};
+
+/// Schema version of SuperContract state
+pub type SchemaVersion = u32;
+
+/// State envelope saved to Storage
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Envelope {
+    schema_version: SchemaVersion,
+    payload: Value,
+}
+
+type Migration = Box<dyn Fn(Value) -> Result<Value>>;
+
+/// Registry of state migrations. Every migration
+/// transform state from version `N` to `N + 1`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use xpx_supercontracts_sdk::storage::state::Migrations;
+///
+/// #[derive(Deserialize)]
+/// struct ConfigV1 {
+///     fee: i64,
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Config {
+///     fee: i64,
+///     fee_asset: u64,
+/// }
+///
+/// // State of previous SuperContract version saved without envelope
+/// let migrations = Migrations::new(2)
+///     .legacy_version(1)
+///     .migrate_from(1, |old: ConfigV1| Config {
+///         fee: old.fee,
+///         fee_asset: 0,
+///     });
+/// let config: Option<Config> = migrations.load(&"config.json".to_string()).unwrap();
+/// ```
+pub struct Migrations {
+    current: SchemaVersion,
+    legacy: SchemaVersion,
+    migrations: Vec<(SchemaVersion, Migration)>,
+}
+
+impl Migrations {
+    /// Create registry for current state schema version
+    pub fn new(current: SchemaVersion) -> Self {
+        Migrations {
+            current,
+            legacy: 0,
+            migrations: vec![],
+        }
+    }
+
+    /// Current state schema version
+    pub fn current(&self) -> SchemaVersion {
+        self.current
+    }
+
+    /// Schema version of state saved without envelope.
+    /// Default is `0`.
+    pub fn legacy_version(mut self, version: SchemaVersion) -> Self {
+        self.legacy = version;
+        self
+    }
+
+    /// Register migration from `version` to `version + 1`
+    pub fn migrate_from<O, N, F>(mut self, version: SchemaVersion, migration: F) -> Self
+    where
+        O: DeserializeOwned,
+        N: Serialize,
+        F: Fn(O) -> N + 'static,
+    {
+        let migration = move |value: Value| -> Result<Value> {
+            let old: O = serde_json::from_value(value).map_err(|_| Error::DeserializeJson)?;
+            serde_json::to_value(migration(old)).map_err(|_| Error::SerializeJson)
+        };
+        self.migrations.retain(|(v, _)| *v != version);
+        self.migrations.push((version, Box::new(migration)));
+        self
+    }
+
+    /// Run migrations for state payload of specific version
+    pub fn migrate(&self, mut version: SchemaVersion, mut payload: Value) -> Result<Value> {
+        if version > self.current {
+            return Err(Error::UnsupportedSchemaVersion);
+        }
+        while version < self.current {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|(v, _)| *v == version)
+                .ok_or(Error::UnsupportedSchemaVersion)?;
+            payload = (migration.1)(payload)?;
+            version += 1;
+        }
+        Ok(payload)
+    }
+
+    /// Load state from Storage and migrate it to current
+    /// schema version. Return `None` if file not exist or empty.
+    /// Migrated state isn't saved automatically, to persist it
+    /// use `save`.
+    pub fn load<T: DeserializeOwned>(&self, file_name: &String) -> Result<Option<T>> {
+        let data = super::storage_get(file_name)?;
+        if data.is_empty() {
+            return Ok(None);
+        }
+        let (version, payload) = unpack_envelope(&data)?;
+        let version = version.unwrap_or(self.legacy);
+        let payload = self.migrate(version, payload)?;
+        let state = serde_json::from_value(payload).map_err(|_| Error::DeserializeJson)?;
+        Ok(Some(state))
+    }
+
+    /// Save state with current schema version to Storage
+    pub fn save<T: Serialize>(&self, file_name: &String, state: &T) -> FunctionResult {
+        super::save_result(file_name, &pack(self.current, state)?)
+    }
+}
+
+/// Serialize state to envelope with specific schema version
+pub fn pack<T: Serialize>(version: SchemaVersion, state: &T) -> Result<Vec<u8>> {
+    let envelope = Envelope {
+        schema_version: version,
+        payload: serde_json::to_value(state).map_err(|_| Error::SerializeJson)?,
+    };
+    serde_json::to_vec(&envelope).map_err(|_| Error::SerializeJson)
+}
+
+/// Deserialize envelope to schema version and state payload.
+/// Data without envelope has schema version `0`.
+pub fn unpack(data: &[u8]) -> Result<(SchemaVersion, Value)> {
+    let (version, payload) = unpack_envelope(data)?;
+    Ok((version.unwrap_or(0), payload))
+}
+
+/// Schema version is `None` for data without envelope
+fn unpack_envelope(data: &[u8]) -> Result<(Option<SchemaVersion>, Value)> {
+    let value: Value = serde_json::from_slice(data).map_err(|_| Error::DeserializeJson)?;
+    let is_envelope = value.as_object().is_some_and(|obj| {
+        obj.len() == 2 && obj.contains_key("SchemaVersion") && obj.contains_key("Payload")
+    });
+    if !is_envelope {
+        return Ok((None, value));
+    }
+    let envelope: Envelope = serde_json::from_value(value).map_err(|_| Error::DeserializeJson)?;
+    Ok((Some(envelope.schema_version), envelope.payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn migrations() -> Migrations {
+        Migrations::new(2)
+            .legacy_version(1)
+            .migrate_from(0, |old: Value| json!({ "fee": old["Fee"] }))
+            .migrate_from(1, |old: Value| json!({ "fee": old["fee"], "fee_asset": 0 }))
+    }
+
+    #[test]
+    fn pack_unpack_round_trip() {
+        let data = pack(3, &json!({ "fee": 10 })).unwrap();
+        assert_eq!(unpack(&data).unwrap(), (3, json!({ "fee": 10 })));
+    }
+
+    #[test]
+    fn data_without_envelope_is_legacy() {
+        let (version, payload) = unpack_envelope(br#"{"fee":10}"#).unwrap();
+        assert_eq!(version, None);
+        assert_eq!(unpack(br#"{"fee":10}"#).unwrap().0, 0);
+
+        let migrations = migrations();
+        let payload = migrations
+            .migrate(version.unwrap_or(migrations.legacy), payload)
+            .unwrap();
+        assert_eq!(payload, json!({ "fee": 10, "fee_asset": 0 }));
+    }
+
+    #[test]
+    fn migrations_applied_in_order() {
+        let payload = migrations().migrate(0, json!({ "Fee": 10 })).unwrap();
+        assert_eq!(payload, json!({ "fee": 10, "fee_asset": 0 }));
+        let current = json!({ "fee": 10, "fee_asset": 5 });
+        assert_eq!(migrations().migrate(2, current.clone()).unwrap(), current);
+    }
+
+    #[test]
+    fn unknown_version_rejected() {
+        assert!(migrations().migrate(3, json!({})).is_err());
+        let migrations = Migrations::new(2).migrate_from(1, |old: Value| old);
+        assert!(migrations.migrate(0, json!({})).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! Chunked read and write of large Storage files.
+//!
+//! `storage_get` load whole file to memory. For large
+//! files use `open` that return reader with `Read` and `Seek`,
+//! and `create` that return chunked writer for results.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::drive;
+use crate::external;
+use crate::statuses::check_status;
+
+/// Default size of chunk for `StorageWriter`
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+fn io_error<T: std::fmt::Display>(err: T) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("{}", err))
+}
+
+/// Reader of Storage file. Every `read` call fetch
+/// requested range of file from `WasmVM`.
+/// For small reads wrap it to `std::io::BufReader`.
+#[derive(Debug)]
+pub struct StorageReader {
+    file_name: String,
+    position: u64,
+    size: Option<u64>,
+}
+
+impl StorageReader {
+    /// File name of reader
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Get file size. Size fetched once via `drive::metadata`.
+    pub fn size(&mut self) -> io::Result<u64> {
+        if let Some(size) = self.size {
+            return Ok(size);
+        }
+        let meta = drive::metadata(&self.file_name).map_err(io_error)?;
+        let size = meta
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, self.file_name.clone()))?
+            .size;
+        self.size = Some(size);
+        Ok(size)
+    }
+}
+
+impl Read for StorageReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let file_name = self.file_name.as_bytes();
+        let read_len = unsafe {
+            external::get_from_storage_range(
+                file_name.as_ptr(),
+                file_name.len(),
+                self.position,
+                buf.len(),
+                buf.as_mut_ptr(),
+            )
+        };
+        if read_len < 0 || read_len as usize > buf.len() {
+            return Err(io_error(format!("storage read status {}", read_len)));
+        }
+        self.position += read_len as u64;
+        Ok(read_len as usize)
+    }
+}
+
+impl Seek for StorageReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.size()?.checked_add_signed(offset),
+        };
+        let position = position.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to negative position")
+        })?;
+        self.position = position;
+        Ok(position)
+    }
+}
+
+/// Open Storage file for chunked reading.
+/// File always located inside `/root/supercontracts/` directory.
+/// So it should be relative path like: `path/to/my/file.csv`
+///
+/// # Examples
+/// ```rust,no_run
+/// use std::io::{BufRead, BufReader};
+/// use xpx_supercontracts_sdk::storage::open;
+///
+/// let reader = open("export.csv").unwrap();
+/// for line in BufReader::new(reader).lines() {
+///     let line = line.unwrap();
+///     // Process line
+/// }
+/// ```
+pub fn open(file_name: &str) -> crate::statuses::Result<StorageReader> {
+    drive::validate_path(file_name)?;
+    Ok(StorageReader {
+        file_name: file_name.to_string(),
+        position: 0,
+        size: None,
+    })
+}
+
+/// Chunked writer of Supercontract result file.
+/// Data buffered and sent to `WasmVM` by chunks.
+/// Writer flushed on drop, but to handle errors
+/// call `flush` explicitly.
+#[derive(Debug)]
+pub struct StorageWriter {
+    file_name: String,
+    buffer: Vec<u8>,
+    chunk_size: usize,
+}
+
+impl StorageWriter {
+    /// Set chunk size of writer. Buffered data
+    /// sent before chunk size changed.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> io::Result<Self> {
+        self.send_buffer()?;
+        self.chunk_size = chunk_size.max(1);
+        Ok(self)
+    }
+
+    fn send_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let status = drive::append(&self.file_name, &self.buffer).map_err(io_error)?;
+        if status < 0 {
+            return Err(io_error(format!("storage write status {}", status)));
+        }
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl Write for StorageWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buffer.len() >= self.chunk_size {
+            self.send_buffer()?;
+        }
+        let len = buf.len().min(self.chunk_size - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        if self.buffer.len() >= self.chunk_size {
+            self.send_buffer()?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_buffer()
+    }
+}
+
+impl Drop for StorageWriter {
+    fn drop(&mut self) {
+        let _ = self.send_buffer();
+    }
+}
+
+/// Create Supercontract result file for chunked writing.
+/// Similar to `save_result`, but data sent by chunks:
+/// existing file truncated, then every chunk appended
+/// with `drive::append`.
+/// File always located inside `/root/supercontracts/` directory.
+///
+/// # Examples
+/// ```rust,no_run
+/// use std::io::Write;
+/// use xpx_supercontracts_sdk::storage::create;
+///
+/// let mut writer = create("report.csv").unwrap();
+/// writer.write_all(b"id,amount\n").unwrap();
+/// writer.write_all(b"1,100\n").unwrap();
+/// writer.flush().unwrap();
+/// ```
+pub fn create(file_name: &str) -> crate::statuses::Result<StorageWriter> {
+    drive::validate_path(file_name)?;
+    check_status(super::save_result(&file_name.to_string(), &[])?)?;
+    Ok(StorageWriter {
+        file_name: file_name.to_string(),
+        buffer: Vec::new(),
+        chunk_size: DEFAULT_CHUNK_SIZE,
+    })
+}
@@ -0,0 +1,475 @@
+//! # Atomic swaps
+//!
+//! Hash time-locked contract (HTLC) on top of **SecretLock**
+//! and **SecretProof** transactions. Initiator lock mosaics
+//! with hash of secret proof, counterparty lock own mosaics
+//! with the same hash for SuperContract account. Initiator
+//! register counterparty lock with `set_counterparty_lock`,
+//! claim it and reveal proof, so counterparty can claim
+//! initiator lock.
+//! Unclaimed lock returned to owner after expiration.
+//!
+//! Counterparty lock accepted only with agreed mosaic and if
+//! it expire at least `safety_margin` blocks before initiator
+//! lock, so participant has time to claim initiator lock after
+//! proof revealed. Participant verify initiator lock in
+//! `participate` before lock own mosaics.
+//!
+//! All Executors should generate the same proof, so proof is
+//! derived from SuperContract secret and swap id instead of random.
+//! Proof is never saved to Storage, it derived again on claim.
+//!
+//! Swap state persisted in Storage file `swap/<id>.json`.
+
+use ripemd160::Ripemd160;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::{Keccak256, Sha3_256};
+
+use crate::external;
+use crate::statuses::{check_status, Error, Result};
+use crate::storage::{save_result, storage_get};
+use crate::tools::hex;
+use crate::transactions::{get_execution_context, get_secret_lock_info, secret_lock, secret_proof};
+use crate::transactions_type::{
+    Address, Duration, GetSecretLockInfo, HashType, Height, Mosaic, Proof, Secret, SecretLock,
+    SecretLockInfo, SecretProof, HASH_TYPE_HASH_160, HASH_TYPE_KECCAK_256, HASH_TYPE_SHA3_256,
+    HASH_TYPE_SHA_256,
+};
+
+const SECRET_LEN: usize = 32;
+const PROOF_CONTEXT: &[u8] = b"xpx-supercontracts-htlc-proof";
+
+/// Calculate lock secret of proof for specific hash type.
+/// Secret is 32 bytes, `HASH_TYPE_HASH_160` padded with zeros.
+///
+/// # Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::swap::lock_secret;
+/// use xpx_supercontracts_sdk::transactions_type::HASH_TYPE_SHA3_256;
+/// let secret = lock_secret(HASH_TYPE_SHA3_256, b"abc").unwrap();
+/// assert_eq!(secret[..4], [0x3a, 0x98, 0x5d, 0xa7]);
+/// ```
+pub fn lock_secret(hash_type: HashType, proof: &[u8]) -> Result<[u8; 32]> {
+    let mut secret = [0u8; 32];
+    match hash_type {
+        HASH_TYPE_SHA3_256 => secret.copy_from_slice(&Sha3_256::digest(proof)),
+        HASH_TYPE_KECCAK_256 => secret.copy_from_slice(&Keccak256::digest(proof)),
+        HASH_TYPE_HASH_160 => {
+            let hash = Ripemd160::digest(&Sha256::digest(proof));
+            secret[..hash.len()].copy_from_slice(&hash);
+        }
+        HASH_TYPE_SHA_256 => secret.copy_from_slice(&Sha256::digest(&Sha256::digest(proof))),
+        _ => return Err(Error::UnsupportedHashType),
+    }
+    Ok(secret)
+}
+
+/// State of swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SwapState {
+    /// Lock created, waiting claim
+    Locked,
+    /// Lock claimed with proof
+    Claimed,
+    /// Lock expired without claim and returned to owner
+    Expired,
+}
+
+/// Parameters of swap lock
+#[derive(Debug, Clone)]
+pub struct HtlcParams {
+    pub mosaic: Mosaic,
+    /// Recipient of locked mosaic
+    pub recipient: Address,
+    /// Lock duration in blocks. Initiator lock should be
+    /// longer than counterparty lock, usually twice.
+    pub duration: Duration,
+    pub hash_type: HashType,
+    /// Mosaic expected in counterparty lock
+    pub counterparty_mosaic: Mosaic,
+    /// Min blocks between expiration of participant lock
+    /// and expiration of initiator lock
+    pub safety_margin: Duration,
+}
+
+/// Lock of counterparty with the same secret,
+/// claimed by SuperContract
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CounterpartyLock {
+    /// Recipient of counterparty lock, usually SuperContract account
+    pub recipient: Address,
+    /// Is lock claimed with **SecretProof transaction**
+    pub claimed: bool,
+}
+
+/// Hash time-locked swap
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::swap::{Htlc, HtlcParams, SwapState};
+/// use xpx_supercontracts_sdk::transactions::get_execution_context;
+/// use xpx_supercontracts_sdk::transactions_type::{Mosaic, HASH_TYPE_SHA3_256};
+///
+/// // Initiator lock own mosaics and share secret with counterparty
+/// let htlc = Htlc::initiate("swap-1", &HtlcParams {
+///     mosaic: Mosaic { asset_id: 992621222383397347, amount: 1000 },
+///     recipient: "VDG7GHF24SZN4FB2C6RRGKRVJ54G5A2NXS7NHMHZ".to_string(),
+///     duration: 480,
+///     hash_type: HASH_TYPE_SHA3_256,
+///     counterparty_mosaic: Mosaic { asset_id: 6300565133566699912, amount: 500 },
+///     safety_margin: 60,
+/// })
+/// .unwrap();
+/// let secret = htlc.secret;
+///
+/// // Counterparty lock own mosaics with the same secret for
+/// // SuperContract account. In the next executions register
+/// // counterparty lock and claim it.
+/// let mut htlc = Htlc::load("swap-1").unwrap().unwrap();
+/// let _ = htlc.set_counterparty_lock("VCKF5QZYBUW7QKIS6PXGMGGBQNUBPU6DW4ZIRN6P");
+/// if let Some(height) = get_execution_context().unwrap().height {
+///     if htlc.refresh(height).unwrap() == SwapState::Locked {
+///         let _ = htlc.claim(None);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Htlc {
+    pub id: String,
+    /// Is swap created with `initiate`. Proof of initiator
+    /// derived from SuperContract secret and never saved.
+    pub initiator: bool,
+    pub hash_type: HashType,
+    /// Lock secret as hex string
+    pub secret: String,
+    pub mosaic: Mosaic,
+    pub recipient: Address,
+    pub duration: Duration,
+    pub counterparty_mosaic: Mosaic,
+    pub safety_margin: Duration,
+    /// State of own lock
+    pub state: SwapState,
+    pub counterparty: Option<CounterpartyLock>,
+}
+
+impl Htlc {
+    /// Generate proof, send to transaction pool **SecretLock transaction**
+    /// and save swap state.
+    pub fn initiate(id: &str, params: &HtlcParams) -> Result<Htlc> {
+        let secret = lock_secret(params.hash_type, &derive_proof(id)?)?;
+        Htlc::lock(id, params, hex::encode(&secret), None)
+    }
+
+    /// Send to transaction pool **SecretLock transaction** with secret
+    /// of initiator lock and save swap state. Initiator lock with
+    /// `recipient`, usually SuperContract account, registered as
+    /// counterparty lock. Proof unknown until initiator claim own lock.
+    ///
+    /// Return `Error::InvalidSwapState` without lock if initiator
+    /// lock not found, used, doesn't have `counterparty_mosaic`
+    /// or expire earlier than `safety_margin` blocks after own lock.
+    pub fn participate(
+        id: &str,
+        secret: &str,
+        recipient: &str,
+        params: &HtlcParams,
+    ) -> Result<Htlc> {
+        if hex::decode(secret).map_or(true, |secret| secret.len() != SECRET_LEN) {
+            return Err(Error::DecodeHex);
+        }
+        let secret = secret.to_uppercase();
+        let height = get_execution_context()?
+            .height
+            .ok_or(Error::UnknownHeight)?;
+        let lock = get_secret_lock_info(&GetSecretLockInfo {
+            secret: secret.clone(),
+            recipient: recipient.to_string(),
+        })?;
+        match lock {
+            Some(lock)
+                if has_terms(&lock, params.hash_type, &params.counterparty_mosaic)
+                    && expires_before(
+                        height.saturating_add(params.duration),
+                        lock.height,
+                        params.safety_margin,
+                    ) => {}
+            _ => return Err(Error::InvalidSwapState),
+        }
+        let counterparty = CounterpartyLock {
+            recipient: recipient.to_string(),
+            claimed: false,
+        };
+        Htlc::lock(id, params, secret, Some(counterparty))
+    }
+
+    fn lock(
+        id: &str,
+        params: &HtlcParams,
+        secret: String,
+        counterparty: Option<CounterpartyLock>,
+    ) -> Result<Htlc> {
+        if Htlc::load(id)?.is_some() {
+            return Err(Error::InvalidSwapState);
+        }
+        check_status(secret_lock(&SecretLock {
+            mosaic: Some(params.mosaic.clone()),
+            duration: params.duration,
+            secret: Some(Secret {
+                hash: secret.clone(),
+                hash_type: params.hash_type,
+            }),
+            recipient: Some(params.recipient.clone()),
+        })?)?;
+        let htlc = Htlc {
+            id: id.to_string(),
+            initiator: counterparty.is_none(),
+            hash_type: params.hash_type,
+            secret,
+            mosaic: params.mosaic.clone(),
+            recipient: params.recipient.clone(),
+            duration: params.duration,
+            counterparty_mosaic: params.counterparty_mosaic.clone(),
+            safety_margin: params.safety_margin,
+            state: SwapState::Locked,
+            counterparty,
+        };
+        htlc.save()?;
+        Ok(htlc)
+    }
+
+    /// Swap state file name in Storage
+    pub fn file_name(id: &str) -> String {
+        format!("swap/{}.json", id)
+    }
+
+    /// Load swap state. Return `None` if swap not exist.
+    pub fn load(id: &str) -> Result<Option<Htlc>> {
+        let data = storage_get(&Htlc::file_name(id))?;
+        if data.is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(|_| Error::DeserializeJson)
+    }
+
+    /// Save swap state to Storage
+    pub fn save(&self) -> Result<()> {
+        let data = serde_json::to_vec(self).map_err(|_| Error::SerializeJson)?;
+        check_status(save_result(&Htlc::file_name(&self.id), &data)?)?;
+        Ok(())
+    }
+
+    /// Current state of own lock on Blockchain
+    pub fn lock_status(&self) -> Result<Option<SecretLockInfo>> {
+        get_secret_lock_info(&GetSecretLockInfo {
+            secret: self.secret.clone(),
+            recipient: self.recipient.clone(),
+        })
+    }
+
+    /// Current state of counterparty lock on Blockchain.
+    /// Return `None` if counterparty lock not registered.
+    pub fn counterparty_lock_status(&self) -> Result<Option<SecretLockInfo>> {
+        match self.counterparty.as_ref() {
+            Some(counterparty) => get_secret_lock_info(&GetSecretLockInfo {
+                secret: self.secret.clone(),
+                recipient: counterparty.recipient.clone(),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Register lock of counterparty with the same secret and
+    /// `recipient`, usually SuperContract account. Only initiator
+    /// register counterparty lock, participant register it in
+    /// `participate`. Return `Error::InvalidSwapState` if lock not
+    /// found, used, doesn't have `counterparty_mosaic` or expire
+    /// later than `safety_margin` blocks before own lock.
+    pub fn set_counterparty_lock(&mut self, recipient: &str) -> Result<()> {
+        if !self.initiator || self.counterparty.as_ref().is_some_and(|lock| lock.claimed) {
+            return Err(Error::InvalidSwapState);
+        }
+        let own_lock = self.lock_status()?.ok_or(Error::InvalidSwapState)?;
+        let lock = get_secret_lock_info(&GetSecretLockInfo {
+            secret: self.secret.clone(),
+            recipient: recipient.to_string(),
+        })?;
+        match lock {
+            Some(lock)
+                if has_terms(&lock, self.hash_type, &self.counterparty_mosaic)
+                    && expires_before(lock.height, own_lock.height, self.safety_margin) => {}
+            _ => return Err(Error::InvalidSwapState),
+        }
+        self.counterparty = Some(CounterpartyLock {
+            recipient: recipient.to_string(),
+            claimed: false,
+        });
+        self.save()
+    }
+
+    /// Update own lock state from lock status at the given
+    /// Blockchain `height` and save it.
+    pub fn refresh(&mut self, height: Height) -> Result<SwapState> {
+        if self.state != SwapState::Locked {
+            return Ok(self.state);
+        }
+        let state = match self.lock_status()? {
+            Some(lock) if lock.is_used() => SwapState::Claimed,
+            Some(lock) if lock.is_expired(height) => SwapState::Expired,
+            _ => SwapState::Locked,
+        };
+        if state != self.state {
+            self.state = state;
+            self.save()?;
+        }
+        Ok(state)
+    }
+
+    /// Send to transaction pool **SecretProof transaction** for
+    /// counterparty lock registered with `set_counterparty_lock`.
+    /// Initiator proof derived again from SuperContract secret.
+    /// Proof required if swap created with `participate`,
+    /// usually it revealed by counterparty claim of own lock.
+    pub fn claim(&mut self, proof: Option<&[u8]>) -> Result<()> {
+        let recipient = match self.counterparty.as_ref() {
+            Some(counterparty) if !counterparty.claimed => counterparty.recipient.clone(),
+            _ => return Err(Error::InvalidSwapState),
+        };
+        let proof = match proof {
+            Some(proof) => proof.to_vec(),
+            None if self.initiator => derive_proof(&self.id)?.to_vec(),
+            None => return Err(Error::InvalidSwapState),
+        };
+        if hex::encode(&lock_secret(self.hash_type, &proof)?) != self.secret {
+            return Err(Error::InvalidSwapState);
+        }
+        match self.counterparty_lock_status()? {
+            Some(lock) if !lock.is_used() => {}
+            _ => return Err(Error::InvalidSwapState),
+        }
+        check_status(secret_proof(&SecretProof {
+            hash_type: self.hash_type,
+            proof: Some(Proof { data: proof }),
+            recipient: Some(recipient),
+        })?)?;
+        if let Some(counterparty) = self.counterparty.as_mut() {
+            counterparty.claimed = true;
+        }
+        self.save()
+    }
+}
+
+/// Is lock unused and created with agreed hash type and mosaic
+fn has_terms(lock: &SecretLockInfo, hash_type: HashType, mosaic: &Mosaic) -> bool {
+    !lock.is_used()
+        && lock.hash_type == hash_type
+        && lock.mosaic.as_ref().is_some_and(|locked| {
+            locked.asset_id == mosaic.asset_id && locked.amount == mosaic.amount
+        })
+}
+
+/// Is lock expiring at `height` leave at least `margin`
+/// blocks before lock expiring at `later`
+fn expires_before(height: Height, later: Height, margin: Duration) -> bool {
+    height
+        .checked_add(margin)
+        .is_some_and(|height| height <= later)
+}
+
+fn derive_proof(id: &str) -> Result<[u8; 32]> {
+    let mut contract_secret = vec![0u8; SECRET_LEN * 2];
+    let secret_len = unsafe { external::get_contract_secret(contract_secret.as_mut_ptr()) };
+    if secret_len != SECRET_LEN as i64 {
+        return Err(Error::InvalidSwapState);
+    }
+    let mut hasher = Sha256::new();
+    hasher.input(PROOF_CONTEXT);
+    hasher.input(&contract_secret[..SECRET_LEN]);
+    hasher.input(id.as_bytes());
+    let mut proof = [0u8; 32];
+    proof.copy_from_slice(&hasher.result());
+    Ok(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions_type::LOCK_STATUS_USED;
+
+    fn lock(asset_id: u64, amount: i64) -> SecretLockInfo {
+        SecretLockInfo {
+            owner: None,
+            mosaic: Some(Mosaic { asset_id, amount }),
+            height: 1000,
+            status: 0,
+            hash_type: HASH_TYPE_SHA3_256,
+            secret: String::new(),
+            recipient: String::new(),
+            composite_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn lock_secret_known_answers() {
+        let cases = [
+            (
+                HASH_TYPE_SHA3_256,
+                "3A985DA74FE225B2045C172D6BD390BD855F086E3E9D525B46BFE24511431532",
+            ),
+            (
+                HASH_TYPE_KECCAK_256,
+                "4E03657AEA45A94FC7D47BA826C8D667C0D1E6E33A64A036EC44F58FA12D6C45",
+            ),
+            (
+                HASH_TYPE_HASH_160,
+                "BB1BE98C142444D7A56AA3981C3942A978E4DC33000000000000000000000000",
+            ),
+            (
+                HASH_TYPE_SHA_256,
+                "4F8B42C22DD3729B519BA6F68D2DA7CC5B2D606D05DAED5AD5128CC03E6C6358",
+            ),
+        ];
+        for (hash_type, secret) in cases.iter() {
+            let result = lock_secret(*hash_type, b"abc").unwrap();
+            assert_eq!(hex::encode(&result), *secret, "{}", hash_type);
+        }
+        assert!(matches!(
+            lock_secret(4, b"abc"),
+            Err(Error::UnsupportedHashType)
+        ));
+    }
+
+    #[test]
+    fn lock_terms_checked() {
+        let mosaic = Mosaic {
+            asset_id: 7,
+            amount: 500,
+        };
+        assert!(has_terms(&lock(7, 500), HASH_TYPE_SHA3_256, &mosaic));
+        assert!(!has_terms(&lock(7, 500), HASH_TYPE_SHA_256, &mosaic));
+        assert!(!has_terms(&lock(8, 500), HASH_TYPE_SHA3_256, &mosaic));
+        assert!(!has_terms(&lock(7, 499), HASH_TYPE_SHA3_256, &mosaic));
+
+        let mut used = lock(7, 500);
+        used.status = LOCK_STATUS_USED;
+        assert!(!has_terms(&used, HASH_TYPE_SHA3_256, &mosaic));
+
+        let mut empty = lock(7, 500);
+        empty.mosaic = None;
+        assert!(!has_terms(&empty, HASH_TYPE_SHA3_256, &mosaic));
+    }
+
+    #[test]
+    fn expiration_leave_safety_margin() {
+        assert!(expires_before(900, 1000, 100));
+        assert!(expires_before(800, 1000, 100));
+        assert!(!expires_before(901, 1000, 100));
+        assert!(!expires_before(1000, 1000, 1));
+        assert!(expires_before(1000, 1000, 0));
+        assert!(!expires_before(Height::MAX, Height::MAX, 1));
+    }
+}
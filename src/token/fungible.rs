@@ -0,0 +1,254 @@
+use crate::statuses::{check_status, Error, FunctionResult, Result};
+use crate::transactions::{
+    get_mosaic_id, get_mosaic_info, mosaic_alias, mosaic_definition, mosaic_supply_change,
+    register_root_namespace, register_sub_namespace, transfer_mosaics,
+};
+use crate::transactions_type::{
+    Amount, Duration, GetMosaicID, GetMosaicInfo, Mosaic, MosaicAlias, MosaicDefinition, MosaicId,
+    MosaicProperties, MosaicSupplyChange, MosaicSupplyType, NamespaceId, PubKey, Recipient,
+    RegisterRootNamespace, RegisterSubNamespace, TransferMosaics, ALIAS_LINK,
+    MOSAIC_SUPPLY_DECREASE, MOSAIC_SUPPLY_INCREASE,
+};
+
+use super::{format_amount, namespace_id, parse_amount, MAX_DIVISIBILITY};
+
+/// Direction of mosaic supply change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplyDirection {
+    Increase,
+    Decrease,
+}
+
+impl From<SupplyDirection> for MosaicSupplyType {
+    fn from(direction: SupplyDirection) -> MosaicSupplyType {
+        match direction {
+            SupplyDirection::Increase => MOSAIC_SUPPLY_INCREASE,
+            SupplyDirection::Decrease => MOSAIC_SUPPLY_DECREASE,
+        }
+    }
+}
+
+/// Parameters of new fungible token
+#[derive(Debug, Clone)]
+pub struct FungibleDefinition {
+    /// Public key of mosaic owner, usually SuperContract id
+    pub owner_public_key: PubKey,
+    /// Nonce of mosaic, unique for owner
+    pub nonce: u32,
+    pub divisibility: u8,
+    /// Initial supply in atomic units
+    pub initial_supply: Amount,
+    pub supply_mutable: bool,
+    pub transferable: bool,
+}
+
+/// Fungible token based on mosaic
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::token::{Fungible, FungibleDefinition};
+/// use xpx_supercontracts_sdk::transactions::get_supercontract;
+///
+/// let sc = get_supercontract().unwrap();
+/// let token = Fungible::define(&FungibleDefinition {
+///     owner_public_key: sc.id,
+///     nonce: 1,
+///     divisibility: 6,
+///     initial_supply: 1_000_000_000_000,
+///     supply_mutable: true,
+///     transferable: true,
+/// })
+/// .unwrap();
+/// let _ = token.register_alias("my_token", 1000);
+/// let amount = token.parse_amount("12.5").unwrap();
+/// let pub_key = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string();
+/// let _ = token.transfer(&pub_key, amount);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fungible {
+    pub mosaic_id: MosaicId,
+    pub divisibility: u8,
+}
+
+impl Fungible {
+    /// Token for existing mosaic with known divisibility.
+    /// Return `Error::InvalidAmount` if divisibility
+    /// greater than `MAX_DIVISIBILITY`.
+    pub fn new(mosaic_id: MosaicId, divisibility: u8) -> Result<Self> {
+        if divisibility > MAX_DIVISIBILITY {
+            return Err(Error::InvalidAmount);
+        }
+        Ok(Fungible {
+            mosaic_id,
+            divisibility,
+        })
+    }
+
+    /// Token for existing mosaic. Divisibility loaded
+    /// with `get_mosaic_info`. Return `None` if mosaic
+    /// not exist, and `Error::InvalidAmount` if mosaic
+    /// divisibility greater than `MAX_DIVISIBILITY`.
+    pub fn load(mosaic_id: MosaicId) -> Result<Option<Self>> {
+        let info = get_mosaic_info(&GetMosaicInfo {
+            mosaic_id: Some(mosaic_id),
+        })?;
+        info.map(|info| {
+            let divisibility = info.properties.map_or(0, |props| props.divisibility);
+            Fungible::new(mosaic_id, divisibility)
+        })
+        .transpose()
+    }
+
+    /// Send to transaction pool **MosaicDefinition transaction**
+    /// and **MosaicSupplyChange transaction** for initial supply.
+    /// Return `Error::FunctionFailed` if transaction not accepted by WasmVM.
+    pub fn define(definition: &FungibleDefinition) -> Result<Self> {
+        if definition.divisibility > MAX_DIVISIBILITY || definition.initial_supply < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        check_status(mosaic_definition(&MosaicDefinition {
+            nonce: definition.nonce,
+            owner_public_key: definition.owner_public_key.clone(),
+            mosaic_props: Some(MosaicProperties {
+                supply_mutable: definition.supply_mutable,
+                transferable: definition.transferable,
+                divisibility: definition.divisibility,
+                optional_properties: vec![],
+            }),
+        })?)?;
+        let mosaic_id = get_mosaic_id(&GetMosaicID {
+            nonce: definition.nonce,
+            owner_public_key: definition.owner_public_key.clone(),
+        })?;
+        let token = Fungible::new(mosaic_id, definition.divisibility)?;
+        if definition.initial_supply > 0 {
+            check_status(token.mint(definition.initial_supply)?)?;
+        }
+        Ok(token)
+    }
+
+    /// Register namespace and link it to mosaic. For sub-namespace,
+    /// like `company.token`, parent namespace should exist,
+    /// and `duration` ignored. Return namespace id, or
+    /// `Error::FunctionFailed` if transaction not accepted by WasmVM.
+    pub fn register_alias(&self, name: &str, duration: Duration) -> Result<NamespaceId> {
+        let status = match name.rsplit_once('.') {
+            Some((parent, part)) => register_sub_namespace(&RegisterSubNamespace {
+                namespace_name: part.to_string(),
+                parent_id: Some(namespace_id(parent)),
+            })?,
+            None => register_root_namespace(&RegisterRootNamespace {
+                namespace_name: name.to_string(),
+                duration,
+            })?,
+        };
+        check_status(status)?;
+        let namespace_id = namespace_id(name);
+        check_status(self.link_alias(namespace_id)?)?;
+        Ok(namespace_id)
+    }
+
+    /// Link existing namespace to mosaic
+    pub fn link_alias(&self, namespace_id: NamespaceId) -> FunctionResult {
+        mosaic_alias(&MosaicAlias {
+            mosaic_id: Some(self.mosaic_id),
+            namespace_id: Some(namespace_id),
+            action_type: ALIAS_LINK,
+        })
+    }
+
+    /// Send to transaction pool **MosaicSupplyChange transaction**.
+    /// Amount in atomic units.
+    pub fn change_supply(&self, direction: SupplyDirection, amount: Amount) -> FunctionResult {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        mosaic_supply_change(&MosaicSupplyChange {
+            asset_id: self.mosaic_id,
+            supply_type: direction.into(),
+            delta: amount,
+        })
+    }
+
+    /// Increase supply. Amount in atomic units.
+    pub fn mint(&self, amount: Amount) -> FunctionResult {
+        self.change_supply(SupplyDirection::Increase, amount)
+    }
+
+    /// Decrease supply. Amount in atomic units.
+    pub fn burn(&self, amount: Amount) -> FunctionResult {
+        self.change_supply(SupplyDirection::Decrease, amount)
+    }
+
+    /// Send to transaction pool **Transfer transaction**.
+    /// Amount in atomic units.
+    pub fn transfer(&self, recipient: &PubKey, amount: Amount) -> FunctionResult {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        transfer_mosaics(&TransferMosaics {
+            recipient: Recipient::PubKey(recipient.clone()),
+            mosaics: vec![Mosaic {
+                asset_id: self.mosaic_id,
+                amount,
+            }],
+            message: None,
+        })
+    }
+
+    /// Transfer tokens to several recipients. Amounts in atomic
+    /// units. All amounts validated before any transaction sent.
+    /// Stop at the first transaction not accepted by WasmVM.
+    /// For large lists use `batch::Batch`.
+    pub fn distribute(&self, recipients: &[(PubKey, Amount)]) -> Result<()> {
+        if recipients.iter().any(|(_, amount)| *amount <= 0) {
+            return Err(Error::InvalidAmount);
+        }
+        for (recipient, amount) in recipients.iter() {
+            check_status(self.transfer(recipient, *amount)?)?;
+        }
+        Ok(())
+    }
+
+    /// Current supply in atomic units from `get_mosaic_info`.
+    /// Return `None` if mosaic not exist yet.
+    pub fn supply(&self) -> Result<Option<Amount>> {
+        let info = get_mosaic_info(&GetMosaicInfo {
+            mosaic_id: Some(self.mosaic_id),
+        })?;
+        Ok(info.map(|info| info.supply))
+    }
+
+    /// Parse human-readable amount to atomic units
+    pub fn parse_amount(&self, amount: &str) -> Result<Amount> {
+        parse_amount(amount, self.divisibility)
+    }
+
+    /// Format amount in atomic units as human-readable string
+    pub fn format_amount(&self, amount: Amount) -> String {
+        format_amount(amount, self.divisibility)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisibility_validated() {
+        for divisibility in 0..=MAX_DIVISIBILITY {
+            let token = Fungible::new(1, divisibility).unwrap();
+            assert_eq!(token.divisibility, divisibility);
+        }
+        assert!(Fungible::new(1, MAX_DIVISIBILITY + 1).is_err());
+        assert!(Fungible::new(1, u8::MAX).is_err());
+    }
+
+    #[test]
+    fn amounts_use_token_divisibility() {
+        let token = Fungible::new(1, 2).unwrap();
+        assert_eq!(token.parse_amount("1.25").unwrap(), 125);
+        assert!(token.parse_amount("1.255").is_err());
+        assert_eq!(token.format_amount(125), "1.25");
+    }
+}
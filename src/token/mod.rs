@@ -0,0 +1,165 @@
+//! # Token toolkit
+//!
+//! High-level API for tokens on top of mosaic transactions.
+//! `Fungible` combine `mosaic_definition`, `mosaic_supply_change`,
+//! namespace registration, `mosaic_alias` and `transfer`.
+//! `Unique` issue non-fungible assets with metadata.
+//!
+//! Amounts on Blockchain are integers in atomic units. With
+//! divisibility `d` one whole token is `10^d` atomic units,
+//! use `parse_amount` and `format_amount` for conversion.
+
+mod fungible;
+mod unique;
+
+pub use self::fungible::{Fungible, FungibleDefinition, SupplyDirection};
+pub use self::unique::{Unique, UniqueAsset, UniqueDefinition, METADATA_CID, METADATA_URI};
+
+use sha3::{Digest, Sha3_256};
+
+use crate::statuses::{Error, Result};
+use crate::transactions_type::{Amount, NamespaceId};
+
+/// Max divisibility of mosaic
+pub const MAX_DIVISIBILITY: u8 = 6;
+
+/// Calculate namespace id of full namespace name, like
+/// `prx.xpx`. Id is the same as calculated by Blockchain.
+///
+/// # Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::token::namespace_id;
+/// assert_eq!(namespace_id("prx.xpx"), 0xBFFB42A19116BDF6);
+/// ```
+pub fn namespace_id(name: &str) -> NamespaceId {
+    name.split('.').fold(0, |parent_id, part| {
+        let mut hasher = Sha3_256::new();
+        hasher.input(parent_id.to_le_bytes());
+        hasher.input(part.as_bytes());
+        let mut id = [0u8; 8];
+        id.copy_from_slice(&hasher.result()[..8]);
+        NamespaceId::from_le_bytes(id) | 1 << 63
+    })
+}
+
+/// Parse human-readable amount, like `12.5`, to atomic
+/// units according to divisibility. Return `Error::InvalidAmount`
+/// if amount has more fraction digits than divisibility,
+/// negative or too big.
+///
+/// # Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::token::parse_amount;
+/// assert_eq!(parse_amount("12.5", 6).unwrap(), 12_500_000);
+/// assert!(parse_amount("0.0000001", 6).is_err());
+/// ```
+pub fn parse_amount(amount: &str, divisibility: u8) -> Result<Amount> {
+    if divisibility > MAX_DIVISIBILITY {
+        return Err(Error::InvalidAmount);
+    }
+    let (whole, fraction) = match amount.trim().split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (amount.trim(), ""),
+    };
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || fraction.len() > divisibility as usize
+    {
+        return Err(Error::InvalidAmount);
+    }
+
+    let mut digits = format!("{}{}", whole, fraction);
+    for _ in fraction.len()..divisibility as usize {
+        digits.push('0');
+    }
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    digits.parse::<Amount>().map_err(|_| Error::InvalidAmount)
+}
+
+/// Format amount in atomic units as human-readable
+/// string according to divisibility. Divisibility isn't
+/// limited by `MAX_DIVISIBILITY`, so any mosaic can be formatted.
+///
+/// # Examples
+/// ```rust
+/// use xpx_supercontracts_sdk::token::format_amount;
+/// assert_eq!(format_amount(12_500_000, 6), "12.5");
+/// assert_eq!(format_amount(3, 0), "3");
+/// ```
+pub fn format_amount(amount: Amount, divisibility: u8) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.unsigned_abs();
+    // Unit greater than any amount for divisibility above 19
+    let (whole, fraction) = match 10u64.checked_pow(divisibility as u32) {
+        Some(unit) => (amount / unit, amount % unit),
+        None => (0, amount),
+    };
+    if fraction == 0 {
+        return format!("{}{}", sign, whole);
+    }
+    let fraction = format!("{:0width$}", fraction, width = divisibility as usize);
+    format!("{}{}.{}", sign, whole, fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespace_ids_match_blockchain() {
+        assert_eq!(namespace_id("nem"), 0x84b3552d375ffa4b);
+        assert_eq!(namespace_id("prx"), 0xb16d77fd8b6fb3be);
+        assert_eq!(namespace_id("prx.xpx"), 0xbffb42a19116bdf6);
+    }
+
+    #[test]
+    fn parse_amount_valid() {
+        assert_eq!(parse_amount("0", 6).unwrap(), 0);
+        assert_eq!(parse_amount("0.000", 3).unwrap(), 0);
+        assert_eq!(parse_amount(" 12.5 ", 6).unwrap(), 12_500_000);
+        assert_eq!(parse_amount(".5", 1).unwrap(), 5);
+        assert_eq!(parse_amount("7.", 2).unwrap(), 700);
+        assert_eq!(parse_amount("007", 0).unwrap(), 7);
+        assert_eq!(parse_amount("9223372036854.775807", 6).unwrap(), Amount::MAX);
+    }
+
+    #[test]
+    fn parse_amount_invalid() {
+        for amount in ["", ".", "-1", "+1", "1e3", "1.2.3", "1,5", "0.0000001"].iter() {
+            assert!(parse_amount(amount, 6).is_err(), "{}", amount);
+        }
+        assert!(parse_amount("9223372036854.775808", 6).is_err());
+        assert!(parse_amount("1", MAX_DIVISIBILITY + 1).is_err());
+    }
+
+    #[test]
+    fn format_amount_round_trip() {
+        assert_eq!(format_amount(0, 6), "0");
+        assert_eq!(format_amount(1, 6), "0.000001");
+        assert_eq!(format_amount(-1_500_000, 6), "-1.5");
+        assert_eq!(format_amount(Amount::MIN, 0), "-9223372036854775808");
+        for amount in [0, 1, 10, 12_500_000, Amount::MAX].iter() {
+            for divisibility in 0..=MAX_DIVISIBILITY {
+                let text = format_amount(*amount, divisibility);
+                assert_eq!(parse_amount(&text, divisibility).unwrap(), *amount);
+            }
+        }
+    }
+
+    #[test]
+    fn format_amount_large_divisibility() {
+        assert_eq!(format_amount(1, 19), "0.0000000000000000001");
+        assert_eq!(format_amount(1, 20), "0.00000000000000000001");
+        assert_eq!(
+            format_amount(-15, u8::MAX),
+            format!("-0.{}15", "0".repeat(253))
+        );
+        assert_eq!(format_amount(0, u8::MAX), "0");
+        assert_eq!(format_amount(Amount::MIN, 20), "-0.09223372036854775808");
+    }
+}
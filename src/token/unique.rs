@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::statuses::{check_status, Error, Result};
+use crate::storage::{save_result, storage_get};
+use crate::transactions::{
+    get_mosaic_id, modify_metadata_mosaic, mosaic_definition, mosaic_supply_change,
+    transfer_mosaics,
+};
+use crate::transactions_type::{
+    Cid, GetMosaicID, MetadataModification, ModifyMetadataMosaic, Mosaic, MosaicDefinition,
+    MosaicId, MosaicProperties, MosaicSupplyChange, PubKey, Recipient, TransferMosaics,
+    METADATA_MODIFICATION_ADD, MOSAIC_SUPPLY_INCREASE,
+};
+
+/// Mosaic metadata key of asset URI
+pub const METADATA_URI: &str = "uri";
+/// Mosaic metadata key of asset content CID
+pub const METADATA_CID: &str = "cid";
+
+/// Unique asset tracked in collection
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UniqueAsset {
+    pub mosaic_id: MosaicId,
+    pub nonce: u32,
+    pub owner: PubKey,
+    pub uri: String,
+    pub content_cid: Option<Cid>,
+}
+
+/// Parameters of new unique asset
+#[derive(Debug, Clone)]
+pub struct UniqueDefinition {
+    /// Nonce of mosaic, unique for collection holder
+    pub nonce: u32,
+    pub uri: String,
+    pub content_cid: Option<Cid>,
+    /// Recipient of asset. If `None` asset held by SuperContract.
+    pub recipient: Option<PubKey>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct CollectionState {
+    assets: BTreeMap<MosaicId, UniqueAsset>,
+}
+
+impl CollectionState {
+    /// Asset that still held by collection holder
+    fn held_asset_mut(&mut self, mosaic_id: MosaicId, holder: &str) -> Result<&mut UniqueAsset> {
+        let asset = self.assets.get_mut(&mosaic_id).ok_or(Error::UnknownAsset)?;
+        if !asset.owner.eq_ignore_ascii_case(holder) {
+            return Err(Error::AssetNotHeld);
+        }
+        Ok(asset)
+    }
+
+    fn assets_of(self, owner: &str) -> Vec<UniqueAsset> {
+        self.assets
+            .into_values()
+            .filter(|asset| asset.owner.eq_ignore_ascii_case(owner))
+            .collect()
+    }
+}
+
+/// Collection of unique assets (NFT). Every asset is mosaic
+/// with divisibility `0`, supply `1` and immutable supply.
+/// Asset URI and content CID attached as mosaic metadata.
+///
+/// Collection issued and held by `holder`, usually SuperContract
+/// id. Owners tracked in Storage file `token/unique/<name>.json`.
+/// SuperContract can transfer only assets it still hold, so
+/// ownership is accurate while assets moved by collection.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::token::{Unique, UniqueDefinition};
+/// use xpx_supercontracts_sdk::transactions::get_supercontract;
+///
+/// let sc = get_supercontract().unwrap();
+/// let certificates = Unique::new("certificates", &sc.id);
+/// let asset = certificates
+///     .issue(&UniqueDefinition {
+///         nonce: 1,
+///         uri: "https://example.com/certificates/1".to_string(),
+///         content_cid: Some("QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH".to_string()),
+///         recipient: None,
+///     })
+///     .unwrap();
+/// let owner = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string();
+/// let _ = certificates.transfer(asset.mosaic_id, &owner);
+/// assert_eq!(certificates.owner_of(asset.mosaic_id).unwrap(), Some(owner));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Unique {
+    name: String,
+    holder: PubKey,
+}
+
+impl Unique {
+    /// Collection with unique name and holder public key
+    pub fn new(name: &str, holder: &PubKey) -> Self {
+        Unique {
+            name: name.to_string(),
+            holder: holder.to_uppercase(),
+        }
+    }
+
+    /// Collection file name in Storage
+    pub fn file_name(&self) -> String {
+        format!("token/unique/{}.json", self.name)
+    }
+
+    fn load(&self) -> Result<CollectionState> {
+        let data = storage_get(&self.file_name())?;
+        if data.is_empty() {
+            return Ok(CollectionState::default());
+        }
+        serde_json::from_slice(&data).map_err(|_| Error::DeserializeJson)
+    }
+
+    fn save(&self, state: &CollectionState) -> Result<()> {
+        let data = serde_json::to_vec(state).map_err(|_| Error::SerializeJson)?;
+        check_status(save_result(&self.file_name(), &data)?)?;
+        Ok(())
+    }
+
+    /// Send to transaction pool transactions that create asset:
+    /// **MosaicDefinition**, **MosaicSupplyChange**, **ModifyMetadataMosaic**
+    /// and **Transfer** if recipient defined. Asset added to collection.
+    /// Return `Error::AssetAlreadyExists` without sending transactions
+    /// if asset with the same nonce already issued, and
+    /// `Error::FunctionFailed` if transaction not accepted by WasmVM.
+    pub fn issue(&self, definition: &UniqueDefinition) -> Result<UniqueAsset> {
+        let mosaic_id = get_mosaic_id(&GetMosaicID {
+            nonce: definition.nonce,
+            owner_public_key: self.holder.clone(),
+        })?;
+        let mut state = self.load()?;
+        if state.assets.contains_key(&mosaic_id) {
+            return Err(Error::AssetAlreadyExists);
+        }
+
+        check_status(mosaic_definition(&MosaicDefinition {
+            nonce: definition.nonce,
+            owner_public_key: self.holder.clone(),
+            mosaic_props: Some(MosaicProperties {
+                supply_mutable: false,
+                transferable: true,
+                divisibility: 0,
+                optional_properties: vec![],
+            }),
+        })?)?;
+        check_status(mosaic_supply_change(&MosaicSupplyChange {
+            asset_id: mosaic_id,
+            supply_type: MOSAIC_SUPPLY_INCREASE,
+            delta: 1,
+        })?)?;
+        check_status(modify_metadata_mosaic(&ModifyMetadataMosaic {
+            mosaic_id: Some(mosaic_id),
+            modifications: Some(metadata_modifications(definition)),
+        })?)?;
+
+        let mut asset = UniqueAsset {
+            mosaic_id,
+            nonce: definition.nonce,
+            owner: self.holder.clone(),
+            uri: definition.uri.clone(),
+            content_cid: definition.content_cid.clone(),
+        };
+        if let Some(recipient) = definition.recipient.as_ref() {
+            send(mosaic_id, recipient)?;
+            asset.owner = recipient.to_uppercase();
+        }
+
+        state.assets.insert(mosaic_id, asset.clone());
+        self.save(&state)?;
+        Ok(asset)
+    }
+
+    /// Send to transaction pool **Transfer transaction** of asset.
+    /// Return `Error::AssetNotHeld` if asset already transferred
+    /// from holder.
+    pub fn transfer(&self, mosaic_id: MosaicId, recipient: &PubKey) -> Result<()> {
+        let mut state = self.load()?;
+        let asset = state.held_asset_mut(mosaic_id, &self.holder)?;
+        send(mosaic_id, recipient)?;
+        asset.owner = recipient.to_uppercase();
+        self.save(&state)
+    }
+
+    /// Asset of collection
+    pub fn asset(&self, mosaic_id: MosaicId) -> Result<Option<UniqueAsset>> {
+        Ok(self.load()?.assets.remove(&mosaic_id))
+    }
+
+    /// Owner of asset. Return `None` if asset not in collection.
+    pub fn owner_of(&self, mosaic_id: MosaicId) -> Result<Option<PubKey>> {
+        Ok(self.asset(mosaic_id)?.map(|asset| asset.owner))
+    }
+
+    /// All assets of collection sorted by mosaic id
+    pub fn assets(&self) -> Result<Vec<UniqueAsset>> {
+        Ok(self.load()?.assets.into_values().collect())
+    }
+
+    /// Assets of specific owner sorted by mosaic id
+    pub fn assets_of(&self, owner: &str) -> Result<Vec<UniqueAsset>> {
+        Ok(self.load()?.assets_of(owner))
+    }
+}
+
+fn metadata_modifications(definition: &UniqueDefinition) -> Vec<MetadataModification> {
+    let mut modifications = vec![MetadataModification {
+        modification_type: METADATA_MODIFICATION_ADD,
+        key: METADATA_URI.to_string(),
+        value: definition.uri.clone(),
+    }];
+    if let Some(cid) = definition.content_cid.as_ref() {
+        modifications.push(MetadataModification {
+            modification_type: METADATA_MODIFICATION_ADD,
+            key: METADATA_CID.to_string(),
+            value: cid.clone(),
+        });
+    }
+    modifications
+}
+
+fn send(mosaic_id: MosaicId, recipient: &PubKey) -> Result<i64> {
+    check_status(transfer_mosaics(&TransferMosaics {
+        recipient: Recipient::PubKey(recipient.clone()),
+        mosaics: vec![Mosaic {
+            asset_id: mosaic_id,
+            amount: 1,
+        }],
+        message: None,
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOLDER: &str = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C";
+    const OWNER: &str = "A1B2C3D4E5F60718293A4B5C6D7E8F90A1B2C3D4E5F60718293A4B5C6D7E8F90";
+
+    fn asset(mosaic_id: MosaicId, owner: &str) -> UniqueAsset {
+        UniqueAsset {
+            mosaic_id,
+            nonce: mosaic_id as u32,
+            owner: owner.to_string(),
+            uri: format!("https://example.com/{}", mosaic_id),
+            content_cid: None,
+        }
+    }
+
+    fn collection() -> CollectionState {
+        let mut state = CollectionState::default();
+        for (mosaic_id, owner) in [(3, HOLDER), (1, OWNER), (2, HOLDER)].iter() {
+            state.assets.insert(*mosaic_id, asset(*mosaic_id, owner));
+        }
+        state
+    }
+
+    #[test]
+    fn only_held_asset_transferable() {
+        let mut state = collection();
+        assert!(state.held_asset_mut(2, &HOLDER.to_lowercase()).is_ok());
+        assert!(matches!(
+            state.held_asset_mut(1, HOLDER),
+            Err(Error::AssetNotHeld)
+        ));
+        assert!(matches!(
+            state.held_asset_mut(4, HOLDER),
+            Err(Error::UnknownAsset)
+        ));
+    }
+
+    #[test]
+    fn assets_of_owner_sorted() {
+        let ids = |assets: Vec<UniqueAsset>| -> Vec<MosaicId> {
+            assets.iter().map(|asset| asset.mosaic_id).collect()
+        };
+        assert_eq!(ids(collection().assets_of(HOLDER)), vec![2, 3]);
+        assert_eq!(ids(collection().assets_of(&OWNER.to_lowercase())), vec![1]);
+        assert!(collection().assets_of("unknown").is_empty());
+    }
+
+    #[test]
+    fn collection_state_round_trip() {
+        let state = collection();
+        let data = serde_json::to_vec(&state).unwrap();
+        assert!(String::from_utf8_lossy(&data).contains("\"MosaicId\":1"));
+        let loaded: CollectionState = serde_json::from_slice(&data).unwrap();
+        assert_eq!(loaded.assets, state.assets);
+    }
+
+    #[test]
+    fn metadata_has_cid_only_if_defined() {
+        let mut definition = UniqueDefinition {
+            nonce: 1,
+            uri: "https://example.com/1".to_string(),
+            content_cid: None,
+            recipient: None,
+        };
+        let modifications = metadata_modifications(&definition);
+        assert_eq!(modifications.len(), 1);
+        assert_eq!(modifications[0].key, METADATA_URI);
+        assert_eq!(modifications[0].value, definition.uri);
+
+        definition.content_cid = Some("QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH".to_string());
+        let modifications = metadata_modifications(&definition);
+        assert_eq!(modifications.len(), 2);
+        assert_eq!(modifications[1].key, METADATA_CID);
+        assert_eq!(
+            Some(&modifications[1].value),
+            definition.content_cid.as_ref()
+        );
+        assert!(modifications
+            .iter()
+            .all(|modification| modification.modification_type == METADATA_MODIFICATION_ADD));
+    }
+}
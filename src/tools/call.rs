@@ -66,3 +66,26 @@ where
     let fn_params_body = fn_param.unwrap();
     unsafe { Ok(extenral_fn(fn_params_body.as_ptr(), fn_params_body.len())) }
 }
+
+/// External function type definition for call
+/// getter external FFI functions without params
+type ExternalGetterFn = unsafe extern "C" fn(data: *mut u8) -> i64;
+
+/// Similar call_external_func but without params for external function
+pub fn call_external_getter<U>(extenral_fn: ExternalGetterFn) -> Result<U>
+where
+    U: DeserializeOwned,
+{
+    let fn_result = unsafe {
+        let fn_result: &mut Vec<u8> = &mut vec![];
+        let fn_result_len = extenral_fn(fn_result.as_mut_ptr());
+        let fn_data_bytes = fn_result.get_unchecked_mut(0..fn_result_len as usize);
+        fn_data_bytes.to_vec()
+    };
+
+    let result = serde_json::from_slice(&fn_result[..]);
+    if result.is_err() {
+        return Err(Error::DeserializeJson);
+    }
+    Ok(result.unwrap())
+}
@@ -0,0 +1,54 @@
+//! Hex encoding toolkit for binary data
+//! passed to `WasmVM` as strings.
+
+const HEX_CHARS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Encode bytes to upper case hex string
+pub fn encode(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len() * 2);
+    for byte in data {
+        result.push(HEX_CHARS[(byte >> 4) as usize] as char);
+        result.push(HEX_CHARS[(byte & 0x0f) as usize] as char);
+    }
+    result
+}
+
+/// Decode hex string in any case to bytes.
+/// Return `None` for invalid hex string.
+pub fn decode(data: &str) -> Option<Vec<u8>> {
+    let data = data.as_bytes();
+    if data.len() % 2 != 0 {
+        return None;
+    }
+    let mut result = Vec::with_capacity(data.len() / 2);
+    for pair in data.chunks(2) {
+        let high = (pair[0] as char).to_digit(16)?;
+        let low = (pair[1] as char).to_digit(16)?;
+        result.push((high << 4 | low) as u8);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_upper_case() {
+        assert_eq!(encode(&[]), "");
+        assert_eq!(encode(&[0x00, 0x0f, 0xab, 0xff]), "000FABFF");
+    }
+
+    #[test]
+    fn decode_any_case() {
+        assert_eq!(decode("000fABff"), Some(vec![0x00, 0x0f, 0xab, 0xff]));
+        assert_eq!(decode(""), Some(vec![]));
+    }
+
+    #[test]
+    fn decode_rejects_invalid_hex() {
+        assert_eq!(decode("abc"), None);
+        assert_eq!(decode("zz"), None);
+        assert_eq!(decode("+1"), None);
+    }
+}
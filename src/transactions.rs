@@ -25,7 +25,8 @@ use serde::de::DeserializeOwned;
 use crate::external;
 use crate::statuses::Error;
 use crate::statuses::{FunctionResult, Result};
-use crate::tools::call::{call_external_func, simple_call_external_func};
+use crate::tools::call::{call_external_func, call_external_getter, simple_call_external_func};
+use crate::tools::hex;
 use crate::transactions_type::*;
 
 /// Flush send immediately Transactions from current
@@ -40,6 +41,8 @@ use crate::transactions_type::*;
 /// from Leader of SuperContract execution.
 /// In both case SuperContract will be paused waiting
 /// event from other Executors.
+/// Is current Executor Leader can be checked
+/// with `get_execution_context`.
 ///
 /// ## Examples
 /// ```rust,no_run
@@ -403,6 +406,179 @@ pub fn transfer_with_namespace(params: &TransferWithNamespace) -> FunctionResult
     simple_call_external_func(params, external::transfer_with_namespace)
 }
 
+/// Send to transaction pool **TransferMosaics transaction**
+///
+/// It's generalization of **Transfer** and **TransferWithNamespace**
+/// transactions. Recipient can be public key, address or namespace alias.
+/// Transaction can carry several mosaics and plain, hex or
+/// encrypted message.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		transfer_mosaics,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{
+///		Mosaic, Recipient, TransferMessage, TransferMosaics,
+/// };
+///
+/// let params = TransferMosaics{
+/// 	recipient: Recipient::Address("VAWOEOWTABXR7O3ZAK2XNA5GIBNE6PZIXDAFDWBU".to_string()),
+///		mosaics: vec![
+///			Mosaic{ asset_id: 10, amount: 1000 },
+///			Mosaic{ asset_id: 20, amount: 50 },
+///		],
+///		message: Some(TransferMessage::Plain("payout #42".to_string())),
+/// };
+/// // Add transaction to Transactions Pool
+/// let tx_result = transfer_mosaics(&params);
+/// if tx_result.is_err() {
+///     // Some error handling
+/// }
+/// ```
+///
+pub fn transfer_mosaics(params: &TransferMosaics) -> FunctionResult {
+    simple_call_external_func(params, external::transfer_mosaics)
+}
+
+/// Send to transaction pool **DriveFs transaction**
+///
+/// Publish files to SuperContract Drive as Drive file system
+/// change, so it will be visible in Drive history.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		drive_fs,
+///		get_supercontract,
+///		prepare_drive_fs,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::AddAction;
+///
+/// let sc = get_supercontract().unwrap();
+/// let params = prepare_drive_fs(
+/// 	&sc.drive.drive,
+/// 	"0F1E2D3C4B5A69788796A5B4C3D2E1F00F1E2D3C4B5A69788796A5B4C3D2E1F0",
+/// ).unwrap()
+/// 	.add_file(AddAction::new(
+/// 		"C4B5A69788796A5B4C3D2E1F00F1E2D3C4B5A69788796A5B4C3D2E1F00F1E2D3".to_string(),
+/// 		1024,
+/// 	));
+/// // Add transaction to Transactions Pool
+/// let tx_result = drive_fs(&params);
+/// if tx_result.is_err() {
+///     // Some error handling
+/// }
+/// ```
+///
+pub fn drive_fs(params: &DriveFs) -> FunctionResult {
+    simple_call_external_func(params, external::drive_fs)
+}
+
+/// Prepare **DriveFs transaction** params that change Drive root
+/// from current root, got via `get_drive_info`, to `new_root`.
+/// Return `Error::UnknownDrive` if Drive not found.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{get_supercontract, prepare_drive_fs};
+///
+/// let sc = get_supercontract().unwrap();
+/// let params = prepare_drive_fs(
+/// 	&sc.drive.drive,
+/// 	"0F1E2D3C4B5A69788796A5B4C3D2E1F00F1E2D3C4B5A69788796A5B4C3D2E1F0",
+/// );
+/// ```
+///
+pub fn prepare_drive_fs(drive_key: &PubKey, new_root: &str) -> Result<DriveFs> {
+    let info = get_drive_info(&GetDriveInfo {
+        drive_key: drive_key.clone(),
+    })?
+    .ok_or(Error::UnknownDrive)?;
+    let previous_root = info.root_hash.unwrap_or_default();
+    DriveFs::new(drive_key.clone(), &previous_root, new_root)
+}
+
+/// Encrypt message for recipient via **EncryptMessage**.
+///
+/// SuperContract doesn't hold private key, so message
+/// encrypted by `WasmVM` with SuperContract account key
+/// and recipient public key.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		encrypt_message,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{EncryptMessage, SecureMessage};
+///
+/// let params = EncryptMessage{
+/// 	recipient_public_key: "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string(),
+/// 	message: "48656C6C6F".to_string(),
+/// };
+/// let result = encrypt_message(&params);
+/// let msg: SecureMessage = result.unwrap();
+/// ```
+///
+pub fn encrypt_message(params: &EncryptMessage) -> Result<SecureMessage> {
+    call_external_func(params, external::encrypt_message)
+}
+
+/// Decrypt message addressed to SuperContract account via **DecryptMessage**.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		decrypt_message,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{DecryptMessage, DecryptedMessage};
+///
+/// let params = DecryptMessage{
+/// 	sender_public_key: "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string(),
+/// 	payload: "A1B2C3".to_string(),
+/// };
+/// let result = decrypt_message(&params);
+/// let msg: DecryptedMessage = result.unwrap();
+/// ```
+///
+pub fn decrypt_message(params: &DecryptMessage) -> Result<DecryptedMessage> {
+    call_external_func(params, external::decrypt_message)
+}
+
+impl SecureMessage {
+    /// Encrypt `plaintext` for `recipient` with SuperContract account key.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use xpx_supercontracts_sdk::transactions_type::{
+    ///		Recipient, SecureMessage, TransferMessage, TransferMosaics,
+    /// };
+    ///
+    /// let recipient = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string();
+    /// let msg = SecureMessage::encrypt(&recipient, b"KYC reference 42").unwrap();
+    /// let params = TransferMosaics{
+    /// 	recipient: Recipient::PubKey(recipient),
+    ///		mosaics: vec![],
+    ///		message: Some(TransferMessage::Secure(msg)),
+    /// };
+    /// ```
+    pub fn encrypt(recipient: &PubKey, plaintext: &[u8]) -> Result<SecureMessage> {
+        encrypt_message(&EncryptMessage {
+            recipient_public_key: recipient.clone(),
+            message: hex::encode(plaintext),
+        })
+    }
+
+    /// Decrypt message sent by `sender` to SuperContract account.
+    pub fn decrypt(&self, sender: &PubKey) -> Result<Vec<u8>> {
+        let msg = decrypt_message(&DecryptMessage {
+            sender_public_key: sender.clone(),
+            payload: self.payload.clone(),
+        })?;
+        hex::decode(&msg.message).ok_or(Error::DecodeHex)
+    }
+}
+
 /// Send to transaction pool **ModifyMetadataAddress transaction**
 ///
 /// ## Examples
@@ -524,6 +700,32 @@ pub fn get_exchange_offer_by_asset_id(params: &GetExchangeOfferByAssetId) -> Res
     call_external_func(params, external::get_exchange_offer_by_asset_id)
 }
 
+/// Get data via **GetExchangeOffers**. Return all
+/// offers of order book for specific asset and offer type.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_exchange_offers,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetExchangeOffers, OfferInfo, SELL_OFFER};
+///
+/// let params = GetExchangeOffers{
+/// 	asset_id: 10,
+/// 	offer_type: SELL_OFFER,
+/// };
+/// // Get info data
+/// let result = get_exchange_offers(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let offers: Option<Vec<OfferInfo>> = result.unwrap();
+/// ```
+///
+pub fn get_exchange_offers(params: &GetExchangeOffers) -> Result<Option<Vec<OfferInfo>>> {
+    call_external_func(params, external::get_exchange_offers)
+}
+
 /// Get data via **GetMosaicInfo**
 ///
 /// ## Examples
@@ -715,6 +917,301 @@ pub fn get_mosaic_id(params: &GetMosaicID) -> Result<u64> {
     call_external_func(params, external::get_mosaic_id)
 }
 
+/// Get data via **GetAddressMetadata**
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_address_metadata,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetAddressMetadata, AddressMetadataInfo};
+///
+/// let params = GetAddressMetadata{
+/// 	address: "VAWOEOWTABXR7O3ZAK2XNA5GIBNE6PZIXDAFDWBU".to_string(),
+/// };
+/// // Get info data
+/// let result = get_address_metadata(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let info: Option<AddressMetadataInfo> = result.unwrap();
+/// ```
+///
+pub fn get_address_metadata(params: &GetAddressMetadata) -> Result<Option<AddressMetadataInfo>> {
+    call_external_func(params, external::get_address_metadata)
+}
+
+/// Get data via **GetMosaicMetadata**
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_mosaic_metadata,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetMosaicMetadata, MosaicMetadataInfo};
+///
+/// let params = GetMosaicMetadata{
+/// 	mosaic_id: 10,
+/// };
+/// // Get info data
+/// let result = get_mosaic_metadata(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let info: Option<MosaicMetadataInfo> = result.unwrap();
+/// ```
+///
+pub fn get_mosaic_metadata(params: &GetMosaicMetadata) -> Result<Option<MosaicMetadataInfo>> {
+    call_external_func(params, external::get_mosaic_metadata)
+}
+
+/// Get data via **GetNamespaceMetadata**
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_namespace_metadata,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetNamespaceMetadata, NamespaceMetadataInfo};
+///
+/// let params = GetNamespaceMetadata{
+/// 	namespace_id: 10,
+/// };
+/// // Get info data
+/// let result = get_namespace_metadata(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let info: Option<NamespaceMetadataInfo> = result.unwrap();
+/// ```
+///
+pub fn get_namespace_metadata(
+    params: &GetNamespaceMetadata,
+) -> Result<Option<NamespaceMetadataInfo>> {
+    call_external_func(params, external::get_namespace_metadata)
+}
+
+/// Get data via **GetNamespaceInfo**
+///
+/// Useful to check namespace expiration before
+/// sending **TransferWithNamespace transaction**.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_namespace_info,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetNamespaceInfo, NamespaceInfo};
+///
+/// let params = GetNamespaceInfo{
+/// 	namespace_id: 10,
+/// };
+/// // Get info data
+/// let result = get_namespace_info(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let info: Option<NamespaceInfo> = result.unwrap();
+/// ```
+///
+pub fn get_namespace_info(params: &GetNamespaceInfo) -> Result<Option<NamespaceInfo>> {
+    call_external_func(params, external::get_namespace_info)
+}
+
+/// Get data via **GetNamespacesNames**
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_namespaces_names,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetNamespacesNames, NamespaceName};
+///
+/// let params = GetNamespacesNames{
+/// 	ns_ids: Some(vec![10, 20]),
+/// };
+/// // Get info data
+/// let result = get_namespaces_names(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let info: Option<Vec<NamespaceName>> = result.unwrap();
+/// ```
+///
+pub fn get_namespaces_names(params: &GetNamespacesNames) -> Result<Option<Vec<NamespaceName>>> {
+    call_external_func(params, external::get_namespaces_names)
+}
+
+/// Get data via **GetNamespacesFromAccount**
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_namespaces_from_account,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetNamespacesFromAccount, NamespaceInfo};
+///
+/// let params = GetNamespacesFromAccount{
+/// 	pub_key: "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string(),
+/// };
+/// // Get info data
+/// let result = get_namespaces_from_account(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let info: Option<Vec<NamespaceInfo>> = result.unwrap();
+/// ```
+///
+pub fn get_namespaces_from_account(
+    params: &GetNamespacesFromAccount,
+) -> Result<Option<Vec<NamespaceInfo>>> {
+    call_external_func(params, external::get_namespaces_from_account)
+}
+
+/// Get data via **ResolveMosaicAlias**. Return `MosaicId`
+/// linked to namespace, or `None` if namespace
+/// has no mosaic alias.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		resolve_mosaic_alias,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{ResolveMosaicAlias, MosaicId};
+///
+/// let params = ResolveMosaicAlias{
+/// 	namespace_id: 10,
+/// };
+/// // Get info data
+/// let result = resolve_mosaic_alias(&params);
+/// let mosaic_id: Option<MosaicId> = result.unwrap();
+/// ```
+///
+pub fn resolve_mosaic_alias(params: &ResolveMosaicAlias) -> Result<Option<MosaicId>> {
+    call_external_func(params, external::resolve_mosaic_alias)
+}
+
+/// Get data via **ResolveAddressAlias**. Return `Address`
+/// linked to namespace, or `None` if namespace
+/// has no address alias.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		resolve_address_alias,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{ResolveAddressAlias, Address};
+///
+/// let params = ResolveAddressAlias{
+/// 	namespace_id: 10,
+/// };
+/// // Get info data
+/// let result = resolve_address_alias(&params);
+/// let address: Option<Address> = result.unwrap();
+/// ```
+///
+pub fn resolve_address_alias(params: &ResolveAddressAlias) -> Result<Option<Address>> {
+    call_external_func(params, external::resolve_address_alias)
+}
+
+/// Get data via **GetDriveInfo**
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_drive_info,
+///		get_supercontract,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetDriveInfo, DriveInfo, DRIVE_IN_PROGRESS};
+///
+/// let sc = get_supercontract().unwrap();
+/// let params = GetDriveInfo{
+/// 	drive_key: sc.drive.drive,
+/// };
+/// // Get info data
+/// let result = get_drive_info(&params);
+/// if result.is_err() {
+///     // Some error handling
+/// }
+/// let info: Option<DriveInfo> = result.unwrap();
+/// if let Some(info) = info {
+///     if info.state == DRIVE_IN_PROGRESS {
+///         // Drive is active
+///     }
+/// }
+/// ```
+///
+pub fn get_drive_info(params: &GetDriveInfo) -> Result<Option<DriveInfo>> {
+    call_external_func(params, external::get_drive_info)
+}
+
+/// Get data via **GetDriveFiles**. Return all files
+/// of Drive according to Drive file system transactions.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_drive_files,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetDriveFiles, DriveFile};
+///
+/// let params = GetDriveFiles{
+/// 	drive_key: "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string(),
+/// };
+/// // Get info data
+/// let result = get_drive_files(&params);
+/// let files: Option<Vec<DriveFile>> = result.unwrap();
+/// ```
+///
+pub fn get_drive_files(params: &GetDriveFiles) -> Result<Option<Vec<DriveFile>>> {
+    call_external_func(params, external::get_drive_files)
+}
+
+/// Get data via **GetFileInfo**
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_file_info,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetFileInfo, DriveFile};
+///
+/// let params = GetFileInfo{
+/// 	drive_key: "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string(),
+/// 	cid: "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH".to_string(),
+/// };
+/// // Get info data
+/// let result = get_file_info(&params);
+/// let file: Option<DriveFile> = result.unwrap();
+/// ```
+///
+pub fn get_file_info(params: &GetFileInfo) -> Result<Option<DriveFile>> {
+    call_external_func(params, external::get_file_info)
+}
+
+/// Get state of **SecretLock transaction** by secret and recipient.
+/// Return `None` if lock not exist.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_secret_lock_info,
+/// };
+/// use xpx_supercontracts_sdk::transactions_type::{GetSecretLockInfo, SecretLockInfo};
+///
+/// let params = GetSecretLockInfo{
+/// 	secret: "3FC8BA10229AB5778D05D9C4B7F56676A88BF9295C185ACFC0F961DB5408CAFE".to_string(),
+/// 	recipient: "VDG7GHF24SZN4FB2C6RRGKRVJ54G5A2NXS7NHMHZ".to_string(),
+/// };
+/// // Get info data
+/// let result = get_secret_lock_info(&params);
+/// let lock: Option<SecretLockInfo> = result.unwrap();
+/// ```
+///
+pub fn get_secret_lock_info(params: &GetSecretLockInfo) -> Result<Option<SecretLockInfo>> {
+    call_external_func(params, external::get_secret_lock_info)
+}
+
 /// Get data current SuperContract data
 ///
 /// ## Examples
@@ -771,3 +1268,28 @@ pub fn get_initiator_pubkey() -> Result<String> {
     let initiator: GetInitiatorPubKey = result.unwrap();
     Ok(initiator.public_key)
 }
+
+/// Get context of current SuperContract execution: invoked function,
+/// execution id, initiator, `Gas` limit, Executors and Leader flag.
+///
+/// Only Leader send result of execution, so side effects
+/// outside Blockchain (like HTTP requests) should be done
+/// only by Leader.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::{
+///		get_execution_context,
+/// };
+/// use xpx_supercontracts_sdk::sc_info;
+///
+/// let ctx = get_execution_context().unwrap();
+/// sc_info!("invoked {}", ctx.function_name; execution_id = ctx.execution_id);
+/// if ctx.is_leader {
+///     // Leader-only side effects
+/// }
+/// ```
+///
+pub fn get_execution_context() -> Result<ExecutionContext> {
+    call_external_getter(external::get_execution_context)
+}
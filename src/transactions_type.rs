@@ -7,9 +7,13 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::statuses::{Error, Result};
+use crate::tools::hex;
+
 pub const FUNCTION_RETURN_SUCCESS: i64 = 0;
 pub const FUNCTION_CONSTRUCTOR_FAIL_TO_SAVE: i64 = -39;
 pub const FUNCTION_ERROR: i64 = -1000;
+pub const FUNCTION_PANIC: i64 = -1001;
 
 pub type Cid = String;
 pub type NetworkType = u8;
@@ -28,6 +32,7 @@ pub type AssetId = u64;
 pub type Duration = i64;
 pub type MosaicSupplyType = u8;
 pub type MetadataModificationType = u8;
+pub type MetadataType = u8;
 pub type HashType = u8;
 pub type Message = String;
 pub type OfferType = u8;
@@ -111,12 +116,18 @@ pub struct MosaicAlias {
 	pub action_type: AliasActionType,
 }
 
+pub const ALIAS_LINK: AliasActionType = 0;
+pub const ALIAS_UNLINK: AliasActionType = 1;
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct AddExchangeOffer {
 	pub add_offers: Option<Vec<AddOffer>>,
 }
 
+pub const SELL_OFFER: OfferType = 0;
+pub const BUY_OFFER: OfferType = 1;
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ExchangeConfirmation {
@@ -173,6 +184,9 @@ pub struct MosaicSupplyChange {
 	pub delta: Duration,
 }
 
+pub const MOSAIC_SUPPLY_DECREASE: MosaicSupplyType = 0;
+pub const MOSAIC_SUPPLY_INCREASE: MosaicSupplyType = 1;
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct RegisterRootNamespace {
@@ -187,7 +201,7 @@ pub struct RegisterSubNamespace {
 	pub parent_id: Option<NamespaceId>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Mosaic {
 	pub asset_id: AssetId,
@@ -209,6 +223,56 @@ pub struct SecretLock {
 	pub recipient: Option<Address>,
 }
 
+/// Hash of proof is SHA3-256
+pub const HASH_TYPE_SHA3_256: HashType = 0;
+/// Hash of proof is Keccak-256
+pub const HASH_TYPE_KECCAK_256: HashType = 1;
+/// Hash of proof is RIPEMD-160 of SHA-256, padded to 32 bytes
+pub const HASH_TYPE_HASH_160: HashType = 2;
+/// Hash of proof is double SHA-256
+pub const HASH_TYPE_SHA_256: HashType = 3;
+
+pub type LockStatus = u8;
+
+pub const LOCK_STATUS_UNUSED: LockStatus = 0;
+pub const LOCK_STATUS_USED: LockStatus = 1;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetSecretLockInfo {
+	/// Secret as hex string
+	pub secret: Hash,
+	pub recipient: Address,
+}
+
+/// State of **SecretLock transaction**
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SecretLockInfo {
+	pub owner: Option<PublicAccount>,
+	pub mosaic: Option<Mosaic>,
+	/// Height when lock expire
+	pub height: Height,
+	pub status: LockStatus,
+	pub hash_type: HashType,
+	pub secret: Hash,
+	pub recipient: Address,
+	pub composite_hash: Hash,
+}
+
+impl SecretLockInfo {
+	/// Is lock claimed with **SecretProof transaction**
+	pub fn is_used(&self) -> bool {
+		self.status == LOCK_STATUS_USED
+	}
+
+	/// Is lock expired at the given Blockchain `height`
+	/// without claim. Expired lock returned to owner.
+	pub fn is_expired(&self, height: Height) -> bool {
+		!self.is_used() && height >= self.height
+	}
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Proof {
 	pub data: Vec<u8>,
@@ -230,6 +294,83 @@ pub struct TransferWithNamespace {
 	pub message: Message,
 }
 
+/// Recipient of **TransferMosaics transaction**
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub enum Recipient {
+	PubKey(PubKey),
+	Address(Address),
+	Namespace(NamespaceId),
+}
+
+/// Encrypted message payload as hex string
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SecureMessage {
+	pub payload: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EncryptMessage {
+	pub recipient_public_key: PubKey,
+	/// Plain message bytes as hex string
+	pub message: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DecryptMessage {
+	pub sender_public_key: PubKey,
+	/// Encrypted message payload as hex string
+	pub payload: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DecryptedMessage {
+	/// Plain message bytes as hex string
+	pub message: String,
+}
+
+/// Message attached to **TransferMosaics transaction**
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub enum TransferMessage {
+	/// Plain text message
+	Plain(Message),
+	/// Binary message as hex string
+	Hex(String),
+	/// Encrypted message
+	Secure(SecureMessage),
+}
+
+impl TransferMessage {
+	/// Create hex message from binary data
+	pub fn hex(data: &[u8]) -> Self {
+		TransferMessage::Hex(hex::encode(data))
+	}
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TransferMosaics {
+	pub recipient: Recipient,
+	pub mosaics: Vec<Mosaic>,
+	pub message: Option<TransferMessage>,
+}
+
+impl From<Transfer> for TransferMosaics {
+	fn from(value: Transfer) -> Self {
+		TransferMosaics {
+			recipient: Recipient::PubKey(value.pub_key),
+			mosaics: vec![Mosaic {
+				asset_id: value.asset_id,
+				amount: value.amount,
+			}],
+			message: None,
+		}
+	}
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ModifyMetadataAddress {
@@ -252,6 +393,9 @@ pub struct MetadataModification {
 	pub value: String,
 }
 
+pub const METADATA_MODIFICATION_ADD: MetadataModificationType = 0;
+pub const METADATA_MODIFICATION_REMOVE: MetadataModificationType = 1;
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ModifyMetadataNamespace {
@@ -259,7 +403,7 @@ pub struct ModifyMetadataNamespace {
 	pub modifications: Option<Vec<MetadataModification>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct OfferInfo {
 	#[serde(rename = "Type")]
@@ -293,6 +437,13 @@ pub struct GetExchangeOfferByAssetId {
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
+pub struct GetExchangeOffers {
+	pub asset_id: AssetId,
+	pub offer_type: OfferType,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "PascalCase")]
 pub struct PublicAccount {
 	pub public_key: String,
 }
@@ -385,6 +536,215 @@ pub struct GetInitiatorPubKey {
 	pub public_key: String,
 }
 
+/// Context of current SuperContract execution
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExecutionContext {
+	/// Name of invoked SuperContract function
+	pub function_name: String,
+	/// Unique id of execution, the same for all Executors
+	pub execution_id: Hash,
+	/// Hash of Execute transaction
+	pub transaction_hash: Hash,
+	/// Initiator of Execute transaction
+	pub initiator: PubKey,
+	/// Mosaics transferred to SuperContract account
+	/// by Execute transaction
+	#[serde(default)]
+	pub mosaics: Vec<Mosaic>,
+	/// Blockchain height of Execute transaction.
+	/// `None` if WasmVM doesn't provide height.
+	#[serde(default)]
+	pub height: Option<Height>,
+	/// `Gas` limit of execution
+	pub gas_limit: i64,
+	/// Public key of current Executor
+	pub executor: PubKey,
+	/// All Executors of execution
+	pub executors: Vec<PubKey>,
+	/// Is current Executor Leader of execution
+	pub is_leader: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetAddressMetadata {
+	pub address: Address,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetMosaicMetadata {
+	pub mosaic_id: MosaicId,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetNamespaceMetadata {
+	pub namespace_id: NamespaceId,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AddressMetadataInfo {
+	#[serde(rename = "Type")]
+	pub metadata_type: MetadataType,
+	pub address: Option<Address>,
+	pub fields: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MosaicMetadataInfo {
+	#[serde(rename = "Type")]
+	pub metadata_type: MetadataType,
+	pub mosaic_id: Option<MosaicId>,
+	pub fields: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NamespaceMetadataInfo {
+	#[serde(rename = "Type")]
+	pub metadata_type: MetadataType,
+	pub namespace_id: Option<NamespaceId>,
+	pub fields: HashMap<String, String>,
+}
+
+pub type DriveState = u8;
+
+pub const DRIVE_NOT_STARTED: DriveState = 0;
+pub const DRIVE_PENDING: DriveState = 1;
+pub const DRIVE_IN_PROGRESS: DriveState = 2;
+pub const DRIVE_FINISHED: DriveState = 3;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReplicatorInfo {
+	pub account: Option<PublicAccount>,
+	pub start: Height,
+	pub end: Height,
+	pub deposit: Amount,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DriveInfo {
+	pub drive_key: PubKey,
+	pub owner: Option<PublicAccount>,
+	pub root_hash: Option<Hash>,
+	pub state: DriveState,
+	pub start: Height,
+	pub size: u64,
+	pub occupied_space: u64,
+	pub replicas: u16,
+	pub min_replicators: u16,
+	pub percent_approvers: u8,
+	pub billing_period: Duration,
+	pub billing_price: Amount,
+	pub replicators: Option<Vec<ReplicatorInfo>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetDriveInfo {
+	pub drive_key: PubKey,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct DriveFile {
+	pub cid: Cid,
+	pub size: u64,
+	/// ID of last DriveFsTransaction that changed the file
+	pub transaction_id: Option<TransactionID>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetDriveFiles {
+	pub drive_key: PubKey,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetFileInfo {
+	pub drive_key: PubKey,
+	pub cid: Cid,
+}
+
+pub type NamespaceType = u8;
+pub type AliasType = u8;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NamespaceAlias {
+	#[serde(rename = "Type")]
+	pub alias_type: AliasType,
+	pub mosaic_id: Option<MosaicId>,
+	pub address: Option<Address>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NamespaceInfo {
+	pub namespace_id: Option<NamespaceId>,
+	pub active: bool,
+	pub type_space: NamespaceType,
+	pub depth: u8,
+	pub levels: Option<Vec<NamespaceId>>,
+	pub alias: Option<NamespaceAlias>,
+	pub parent_id: Option<NamespaceId>,
+	pub owner: Option<PublicAccount>,
+	pub start_height: Height,
+	pub end_height: Height,
+}
+
+impl NamespaceInfo {
+	/// Check is namespace expired at the given Blockchain `height`.
+	pub fn is_expired(&self, height: Height) -> bool {
+		!self.active || height >= self.end_height
+	}
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetNamespaceInfo {
+	pub namespace_id: NamespaceId,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NamespaceName {
+	pub namespace_id: Option<NamespaceId>,
+	pub name: String,
+	pub parent_id: Option<NamespaceId>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetNamespacesNames {
+	pub ns_ids: Option<Vec<NamespaceId>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GetNamespacesFromAccount {
+	pub pub_key: PubKey,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResolveMosaicAlias {
+	pub namespace_id: NamespaceId,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResolveAddressAlias {
+	pub namespace_id: NamespaceId,
+}
+
 /// # Transactions
 /// **SignedTransaction** is common interface for transactions data
 /// from Blockchain.
@@ -395,13 +755,127 @@ pub trait SignedTransaction {
 	fn signature(&self) -> Signature;
 }
 
+/// Type of Drive file system action
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "i64", into = "i64")]
+pub enum ActionType {
+	Add,
+	Remove,
+	Unknown(i64),
+}
+
+impl From<i64> for ActionType {
+	fn from(value: i64) -> Self {
+		match value {
+			0 => ActionType::Add,
+			1 => ActionType::Remove,
+			value => ActionType::Unknown(value),
+		}
+	}
+}
+
+impl From<ActionType> for i64 {
+	fn from(value: ActionType) -> Self {
+		match value {
+			ActionType::Add => 0,
+			ActionType::Remove => 1,
+			ActionType::Unknown(value) => value,
+		}
+	}
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Action {
 	pub hash: Hash,
-	pub action_type: i64,
+	pub action_type: ActionType,
+	pub size: u64,
+}
+
+/// Drive file system action with its kind
+#[derive(Debug, Clone)]
+pub enum DriveAction {
+	Add(Action),
+	Remove(Action),
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct AddAction {
+	pub hash: Hash,
+	pub size: u64,
+}
+
+impl AddAction {
+	pub fn new(hash: Hash, size: u64) -> Self {
+		AddAction { hash, size }
+	}
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RemoveAction {
+	pub hash: Hash,
 	pub size: u64,
 }
 
+impl RemoveAction {
+	pub fn new(hash: Hash, size: u64) -> Self {
+		RemoveAction { hash, size }
+	}
+}
+
+/// Params of **DriveFs transaction**. Use `transactions::prepare_drive_fs`
+/// to change current Drive root, or `DriveFs::new` to calculate
+/// `xor_root_hash` from known previous and new root hashes.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct DriveFs {
+	pub drive_key: PubKey,
+	pub root_hash: Hash,
+	pub xor_root_hash: Hash,
+	pub add_actions: Vec<AddAction>,
+	pub remove_actions: Vec<RemoveAction>,
+}
+
+impl DriveFs {
+	/// Create transaction params that change Drive root
+	/// from `previous_root` to `new_root`. Both hashes should
+	/// be hex strings with the same length, otherwise
+	/// `Error::RootHashLength` returned. Empty `previous_root`
+	/// means Drive without root yet.
+	pub fn new(drive_key: PubKey, previous_root: &str, new_root: &str) -> Result<Self> {
+		let new = hex::decode(new_root).ok_or(Error::DecodeHex)?;
+		let previous = if previous_root.is_empty() {
+			vec![0u8; new.len()]
+		} else {
+			hex::decode(previous_root).ok_or(Error::DecodeHex)?
+		};
+		if previous.len() != new.len() {
+			return Err(Error::RootHashLength);
+		}
+		let xor: Vec<u8> = previous.iter().zip(new.iter()).map(|(a, b)| a ^ b).collect();
+		Ok(DriveFs {
+			drive_key,
+			root_hash: hex::encode(&new),
+			xor_root_hash: hex::encode(&xor),
+			add_actions: vec![],
+			remove_actions: vec![],
+		})
+	}
+
+	/// Add file to Drive
+	pub fn add_file(mut self, action: AddAction) -> Self {
+		self.add_actions.push(action);
+		self
+	}
+
+	/// Remove file from Drive
+	pub fn remove_file(mut self, action: RemoveAction) -> Self {
+		self.remove_actions.push(action);
+		self
+	}
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DriveFsTransaction {
 	pub id: TransactionID,
@@ -425,6 +899,18 @@ impl DriveFsTransaction {
 	pub fn remove_actions(&self) -> Option<Vec<Action>> {
 		self.remove_actions.clone()
 	}
+
+	/// All actions of transaction: add actions first, then remove actions
+	pub fn actions(&self) -> Vec<DriveAction> {
+		let add = self.add_actions.iter().flatten().cloned().map(DriveAction::Add);
+		let remove = self
+			.remove_actions
+			.iter()
+			.flatten()
+			.cloned()
+			.map(DriveAction::Remove);
+		add.chain(remove).collect()
+	}
 }
 
 impl SignedTransaction for DriveFsTransaction {
@@ -444,3 +930,36 @@ impl SignedTransaction for DriveFsTransaction {
 		self.signature.clone()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const DRIVE: &str = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C";
+
+	#[test]
+	fn drive_fs_xor_root_hash() {
+		let fs = DriveFs::new(DRIVE.to_string(), "0F0F00", "f0ff00").unwrap();
+		assert_eq!(fs.root_hash, "F0FF00");
+		assert_eq!(fs.xor_root_hash, "FFF000");
+	}
+
+	#[test]
+	fn drive_fs_from_empty_root() {
+		let fs = DriveFs::new(DRIVE.to_string(), "", "ABCD").unwrap();
+		assert_eq!(fs.root_hash, "ABCD");
+		assert_eq!(fs.xor_root_hash, "ABCD");
+	}
+
+	#[test]
+	fn drive_fs_invalid_roots_rejected() {
+		match DriveFs::new(DRIVE.to_string(), "ABCD", "ABCDEF") {
+			Err(Error::RootHashLength) => {}
+			other => panic!("unexpected result: {:?}", other),
+		}
+		match DriveFs::new(DRIVE.to_string(), "ABCD", "XYZW") {
+			Err(Error::DecodeHex) => {}
+			other => panic!("unexpected result: {:?}", other),
+		}
+	}
+}
@@ -1,8 +1,15 @@
 //! Basic utils functions for communication with `WasmVM`.
 
+use std::panic;
+use std::sync::Once;
+
 use crate::external;
-use crate::statuses::FunctionResult;
-use crate::transactions_type::FUNCTION_RETURN_SUCCESS;
+use crate::logging::{field, write_record, Level};
+use crate::statuses::{check_status, FunctionResult, Result};
+use crate::storage::{save_result, storage_get};
+use crate::tools::hex;
+use crate::transactions::get_execution_context;
+use crate::transactions_type::{FUNCTION_PANIC, FUNCTION_RETURN_SUCCESS};
 
 /// Constructor is function for one time call that can
 /// can invoke only once for all lifetime of SuperContract.
@@ -41,6 +48,8 @@ pub fn constructor(constructor_handler: fn() -> i64) -> i64 {
 /// functions to tune-up and prepare some state for SuperContract.
 ///
 /// It's impossible run that function twice.
+/// For several independent one-time steps
+/// use named guards: `once` and `try_once`.
 ///
 /// # Examples
 /// ```rust,no_run
@@ -61,6 +70,135 @@ pub fn init(init_handler: fn() -> ()) {
     init_handler();
 }
 
+/// Scope of named one-shot guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnceScope {
+    /// Guard shared by all SuperContract functions
+    Contract,
+    /// Separate guard for every invoked SuperContract function
+    Function,
+}
+
+/// Run handler only once for all lifetime of SuperContract.
+/// Guard identified by `key`, so SuperContract can have
+/// any number of independent one-time steps. Return `Ok(None)`
+/// if guard with the same key already done, and `Err` if
+/// guard state can't be read or saved. In that case
+/// handler not invoked.
+///
+/// Guard marked as done before handler invoked, so handler
+/// is never invoked twice, even if it failed. To retry
+/// failed handler use `try_once`.
+///
+/// Guards persisted in Storage in `once/` directory.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::mosaic_definition;
+/// use xpx_supercontracts_sdk::transactions_type::MosaicDefinition;
+/// use xpx_supercontracts_sdk::utils::once;
+///
+/// let owner = "2C8178EF9ED7A6D30ABDC1E4D30D68B05861112A98B1629FBE2C8D16FDE97A1C".to_string();
+/// let result = once("create-mosaic", || {
+///     mosaic_definition(&MosaicDefinition {
+///         nonce: 100,
+///         owner_public_key: owner.clone(),
+///         mosaic_props: None,
+///     })
+/// });
+/// match result {
+///     Ok(Some(Ok(status))) => {}
+///     Ok(Some(Err(err))) => {
+///         // Handler failed
+///     }
+///     Ok(None) => {
+///         // Mosaic already created
+///     }
+///     Err(err) => {
+///         // Guard failed, handler not invoked
+///     }
+/// }
+/// ```
+pub fn once<F, R>(key: &str, f: F) -> Result<Option<R>>
+where
+    F: FnOnce() -> R,
+{
+    once_in(OnceScope::Contract, key, f)
+}
+
+/// Run handler only once for specific `OnceScope`.
+/// The same as `once` for `OnceScope::Contract`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::utils::{once_in, ping, OnceScope};
+/// let res = once_in(OnceScope::Function, "warm-up", || ping(10));
+/// ```
+pub fn once_in<F, R>(scope: OnceScope, key: &str, f: F) -> Result<Option<R>>
+where
+    F: FnOnce() -> R,
+{
+    let file_name = once_file_name(scope, key)?;
+    if is_once_done(&file_name)? {
+        return Ok(None);
+    }
+    mark_once_done(&file_name)?;
+    Ok(Some(f()))
+}
+
+/// Run handler until it succeeded once. Guard marked as
+/// done only if handler return `Ok`, so failed handler
+/// will be invoked again in the next execution.
+/// Return `Ok(None)` if guard already done, and `Err`
+/// if handler or guard failed.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::transactions::register_root_namespace;
+/// use xpx_supercontracts_sdk::transactions_type::RegisterRootNamespace;
+/// use xpx_supercontracts_sdk::utils::{try_once, OnceScope};
+///
+/// let result = try_once(OnceScope::Contract, "register-namespace", || {
+///     register_root_namespace(&RegisterRootNamespace {
+///         namespace_name: "my_token".to_string(),
+///         duration: 1000,
+///     })
+/// });
+/// ```
+pub fn try_once<F, T>(scope: OnceScope, key: &str, f: F) -> Result<Option<T>>
+where
+    F: FnOnce() -> Result<T>,
+{
+    let file_name = once_file_name(scope, key)?;
+    if is_once_done(&file_name)? {
+        return Ok(None);
+    }
+    let result = f()?;
+    mark_once_done(&file_name)?;
+    Ok(Some(result))
+}
+
+/// Guard key encoded as hex, so any key is valid file name
+fn once_file_name(scope: OnceScope, key: &str) -> Result<String> {
+    let key = hex::encode(key.as_bytes());
+    match scope {
+        OnceScope::Contract => Ok(format!("once/contract/{}", key)),
+        OnceScope::Function => {
+            let ctx = get_execution_context()?;
+            let function = hex::encode(ctx.function_name.as_bytes());
+            Ok(format!("once/function/{}/{}", function, key))
+        }
+    }
+}
+
+fn is_once_done(file_name: &String) -> Result<bool> {
+    Ok(!storage_get(file_name)?.is_empty())
+}
+
+fn mark_once_done(file_name: &String) -> FunctionResult {
+    check_status(save_result(file_name, b"1")?)
+}
+
 /// Send ping message to `WasmVM`. Successful result should be
 /// incremented value. Useful for most simple request/response
 /// message tests for  `WasmVM`.
@@ -97,14 +235,91 @@ pub fn inc() -> FunctionResult {
 /// removed from `release` version, because it
 /// will spend `Gas` (unit ticks).
 ///
+/// For leveled logging that can be removed at compile
+/// time use `sc_*!` macros from `logging` module.
+///
 /// # Examples
 /// ```rust,no_run
 /// use xpx_supercontracts_sdk::utils::debug_message;
 /// debug_message(&"Debug message from Supercontract".to_string());
 /// ```
-pub fn debug_message(msg: &String) {
+pub fn debug_message(msg: &str) {
     let raw_msg = msg.as_bytes();
     unsafe {
         external::__write_log(raw_msg.as_ptr(), raw_msg.len());
     };
 }
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Install panic hook that report panics to `WasmVM`.
+/// On `wasm32-unknown-unknown` panic trap execution without
+/// any message. With installed hook panic message and location
+/// sent to `WasmVM` log as `ERROR` record, and `WasmVM` receive
+/// exit status `FUNCTION_PANIC`.
+///
+/// Hook installed only once, repeated calls do nothing.
+/// Panic records sent regardless of logging max level.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::utils::{install_panic_hook, ping};
+///
+/// #[no_mangle]
+/// pub extern "C" fn app_main() -> i64 {
+///     install_panic_hook();
+///     ping(10).unwrap()
+/// }
+/// ```
+pub fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        panic::set_hook(Box::new(|info| {
+            let payload = info.payload();
+            let message = if let Some(msg) = payload.downcast_ref::<&str>() {
+                msg.to_string()
+            } else if let Some(msg) = payload.downcast_ref::<String>() {
+                msg.clone()
+            } else {
+                "Box<dyn Any>".to_string()
+            };
+            let mut fields = vec![];
+            if let Some(location) = info.location() {
+                fields.push(("file", field(location.file())));
+                fields.push(("line", field(&location.line())));
+                fields.push(("column", field(&location.column())));
+            }
+            write_record(Level::Error, "panic", &message, &fields);
+            unsafe {
+                external::__set_exit_status(FUNCTION_PANIC);
+            }
+        }));
+    });
+}
+
+/// Declare SuperContract entry function with installed
+/// panic hook. Function exported with `#[no_mangle]`
+/// and should return `i64`.
+///
+/// # Examples
+/// ```rust,no_run
+/// use xpx_supercontracts_sdk::sc_entry;
+/// use xpx_supercontracts_sdk::utils::ping;
+///
+/// sc_entry! {
+///     fn app_main() -> i64 {
+///         ping(10).unwrap()
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! sc_entry {
+    ($(fn $name:ident() -> i64 $body:block)+) => {
+        $(
+            #[no_mangle]
+            pub extern "C" fn $name() -> i64 {
+                $crate::utils::install_panic_hook();
+                $body
+            }
+        )+
+    };
+}